@@ -0,0 +1,374 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    time::{Duration, Instant},
+};
+
+use crate::{ConfirmationId, TcpContract};
+
+const CONFIRM_PACKET_VERSION: u8 = 0;
+
+struct DeliveryConfirmation {
+    topic_id: String,
+    queue_id: String,
+    pending: BTreeSet<i64>,
+    //Messages processed as Ok but not confirmed by IntermediaryConfirm yet
+    ok: BTreeSet<i64>,
+    failed: BTreeSet<i64>,
+    last_intermediary_confirm: Instant,
+}
+
+impl DeliveryConfirmation {
+    fn compile_confirmation(self, confirmation_id: ConfirmationId) -> TcpContract {
+        if self.failed.is_empty() {
+            return TcpContract::NewMessagesConfirmation {
+                topic_id: self.topic_id,
+                queue_id: self.queue_id,
+                confirmation_id,
+            };
+        }
+
+        if self.ok.is_empty() {
+            return TcpContract::AllMessagesConfirmedAsFail {
+                topic_id: self.topic_id,
+                queue_id: self.queue_id,
+                confirmation_id,
+            };
+        }
+
+        TcpContract::ConfirmSomeMessagesAsOk {
+            packet_version: CONFIRM_PACKET_VERSION,
+            topic_id: self.topic_id,
+            queue_id: self.queue_id,
+            confirmation_id,
+            delivered: crate::queue_index_ranges::from_ids(self.ok),
+        }
+    }
+}
+
+pub struct ConfirmationTracker {
+    deliveries: HashMap<ConfirmationId, DeliveryConfirmation>,
+    intermediary_confirm_interval: Option<Duration>,
+}
+
+impl ConfirmationTracker {
+    pub fn new(intermediary_confirm_interval: Option<Duration>) -> Self {
+        Self {
+            deliveries: HashMap::new(),
+            intermediary_confirm_interval,
+        }
+    }
+
+    //Registers delivery of NewMessages or not empty FetchMessagesResponse.
+    //Returns confirmation packet if it has to be sent right away since delivery has no messages to wait for
+    pub fn new_messages(&mut self, contract: &TcpContract, now: Instant) -> Option<TcpContract> {
+        match contract {
+            TcpContract::NewMessages {
+                topic_id,
+                queue_id,
+                confirmation_id,
+                messages,
+                ..
+            } => self.register_delivery(
+                topic_id,
+                queue_id,
+                *confirmation_id,
+                messages.iter().map(|msg| msg.message.id.get_value()),
+                now,
+            ),
+            TcpContract::FetchMessagesResponse {
+                topic_id,
+                queue_id,
+                confirmation_id,
+                messages,
+                ..
+            } => {
+                if messages.is_empty() {
+                    return None;
+                }
+
+                self.register_delivery(
+                    topic_id,
+                    queue_id,
+                    *confirmation_id,
+                    messages.iter().map(|msg| msg.message.id.get_value()),
+                    now,
+                )
+            }
+            _ => None,
+        }
+    }
+
+    //Delivery without messages is not registered - its confirmation packet is returned right away
+    pub fn register_delivery(
+        &mut self,
+        topic_id: &str,
+        queue_id: &str,
+        confirmation_id: ConfirmationId,
+        message_ids: impl Iterator<Item = i64>,
+        now: Instant,
+    ) -> Option<TcpContract> {
+        let delivery = DeliveryConfirmation {
+            topic_id: topic_id.to_string(),
+            queue_id: queue_id.to_string(),
+            pending: message_ids.collect(),
+            ok: BTreeSet::new(),
+            failed: BTreeSet::new(),
+            last_intermediary_confirm: now,
+        };
+
+        if delivery.pending.is_empty() {
+            return Some(delivery.compile_confirmation(confirmation_id));
+        }
+
+        self.deliveries.insert(confirmation_id, delivery);
+        None
+    }
+
+    //Returns true if delivery was tracked
+    pub fn remove_delivery(&mut self, confirmation_id: ConfirmationId) -> bool {
+        self.deliveries.remove(&confirmation_id).is_some()
+    }
+
+    //Has to be called on disconnect since server redelivers not confirmed messages anyway
+    pub fn clear(&mut self) {
+        self.deliveries.clear();
+    }
+
+    //Returns confirmation packet as soon as every message of the delivery is processed
+    pub fn set_ok(
+        &mut self,
+        confirmation_id: ConfirmationId,
+        message_id: i64,
+    ) -> Option<TcpContract> {
+        self.set_result(confirmation_id, message_id, true)
+    }
+
+    //Returns confirmation packet as soon as every message of the delivery is processed
+    pub fn set_fail(
+        &mut self,
+        confirmation_id: ConfirmationId,
+        message_id: i64,
+    ) -> Option<TcpContract> {
+        self.set_result(confirmation_id, message_id, false)
+    }
+
+    pub fn get_intermediary_confirms(&mut self, now: Instant) -> Vec<TcpContract> {
+        let mut result = Vec::new();
+
+        let interval = match self.intermediary_confirm_interval {
+            Some(interval) => interval,
+            None => return result,
+        };
+
+        for (confirmation_id, delivery) in self.deliveries.iter_mut() {
+            if delivery.ok.is_empty() || now < delivery.last_intermediary_confirm + interval {
+                continue;
+            }
+
+            let delivered = crate::queue_index_ranges::from_ids(delivery.ok.iter().copied());
+            delivery.ok.clear();
+            delivery.last_intermediary_confirm = now;
+
+            result.push(TcpContract::IntermediaryConfirm {
+                packet_version: CONFIRM_PACKET_VERSION,
+                topic_id: delivery.topic_id.to_string(),
+                queue_id: delivery.queue_id.to_string(),
+                confirmation_id: *confirmation_id,
+                delivered,
+            });
+        }
+
+        result
+    }
+
+    pub fn get_pending_amount(&self) -> usize {
+        self.deliveries.len()
+    }
+
+    fn set_result(
+        &mut self,
+        confirmation_id: ConfirmationId,
+        message_id: i64,
+        ok: bool,
+    ) -> Option<TcpContract> {
+        let delivery = self.deliveries.get_mut(&confirmation_id)?;
+
+        if !delivery.pending.remove(&message_id) {
+            return None;
+        }
+
+        if ok {
+            delivery.ok.insert(message_id);
+        } else {
+            delivery.failed.insert(message_id);
+        }
+
+        if !delivery.pending.is_empty() {
+            return None;
+        }
+
+        let delivery = self.deliveries.remove(&confirmation_id)?;
+        Some(delivery.compile_confirmation(confirmation_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_messages_ok() {
+        let mut tracker = ConfirmationTracker::new(None);
+        let now = Instant::now();
+
+        tracker.register_delivery("topic", "queue", 1, [10, 11, 12].into_iter(), now);
+
+        assert!(tracker.set_ok(1, 10).is_none());
+        assert!(tracker.set_ok(1, 11).is_none());
+
+        match tracker.set_ok(1, 12).unwrap() {
+            TcpContract::NewMessagesConfirmation {
+                topic_id,
+                queue_id,
+                confirmation_id,
+            } => {
+                assert_eq!("topic", topic_id);
+                assert_eq!("queue", queue_id);
+                assert_eq!(1, confirmation_id);
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+
+        assert_eq!(0, tracker.get_pending_amount());
+    }
+
+    #[test]
+    fn test_some_messages_ok() {
+        let mut tracker = ConfirmationTracker::new(None);
+        let now = Instant::now();
+
+        tracker.register_delivery("topic", "queue", 1, [10, 11, 12, 13].into_iter(), now);
+
+        assert!(tracker.set_ok(1, 10).is_none());
+        assert!(tracker.set_ok(1, 11).is_none());
+        assert!(tracker.set_fail(1, 12).is_none());
+
+        match tracker.set_ok(1, 13).unwrap() {
+            TcpContract::ConfirmSomeMessagesAsOk {
+                confirmation_id,
+                delivered,
+                ..
+            } => {
+                assert_eq!(1, confirmation_id);
+                assert_eq!(2, delivered.len());
+                assert_eq!(10, delivered[0].from_id);
+                assert_eq!(11, delivered[0].to_id);
+                assert_eq!(13, delivered[1].from_id);
+                assert_eq!(13, delivered[1].to_id);
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_intermediary_confirm() {
+        let mut tracker = ConfirmationTracker::new(Some(Duration::from_secs(1)));
+        let now = Instant::now();
+
+        tracker.register_delivery("topic", "queue", 1, [10, 11, 12].into_iter(), now);
+
+        tracker.set_ok(1, 10);
+        tracker.set_ok(1, 11);
+
+        assert_eq!(0, tracker.get_intermediary_confirms(now).len());
+
+        let confirms = tracker.get_intermediary_confirms(now + Duration::from_secs(1));
+        assert_eq!(1, confirms.len());
+
+        match &confirms[0] {
+            TcpContract::IntermediaryConfirm { delivered, .. } => {
+                assert_eq!(1, delivered.len());
+                assert_eq!(10, delivered[0].from_id);
+                assert_eq!(11, delivered[0].to_id);
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+
+        match tracker.set_fail(1, 12).unwrap() {
+            TcpContract::AllMessagesConfirmedAsFail {
+                confirmation_id, ..
+            } => {
+                assert_eq!(1, confirmation_id);
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_empty_delivery_is_confirmed_right_away() {
+        let mut tracker = ConfirmationTracker::new(None);
+        let now = Instant::now();
+
+        let result = tracker.register_delivery("topic", "queue", 1, std::iter::empty(), now);
+
+        match result.unwrap() {
+            TcpContract::NewMessagesConfirmation {
+                topic_id,
+                queue_id,
+                confirmation_id,
+            } => {
+                assert_eq!("topic", topic_id);
+                assert_eq!("queue", queue_id);
+                assert_eq!(1, confirmation_id);
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+
+        assert_eq!(0, tracker.get_pending_amount());
+    }
+
+    #[test]
+    fn test_remove_delivery() {
+        let mut tracker = ConfirmationTracker::new(None);
+        let now = Instant::now();
+
+        assert!(tracker
+            .register_delivery("topic", "queue", 1, [10, 11].into_iter(), now)
+            .is_none());
+        assert!(tracker
+            .register_delivery("topic", "queue", 2, [12].into_iter(), now)
+            .is_none());
+
+        assert!(tracker.remove_delivery(1));
+        assert!(!tracker.remove_delivery(1));
+        assert_eq!(1, tracker.get_pending_amount());
+
+        assert!(tracker.set_ok(1, 10).is_none());
+        assert!(tracker.set_ok(2, 12).is_some());
+    }
+
+    #[test]
+    fn test_clear() {
+        let mut tracker = ConfirmationTracker::new(None);
+        let now = Instant::now();
+
+        tracker.register_delivery("topic", "queue", 1, [10, 11].into_iter(), now);
+        tracker.register_delivery("topic", "queue", 2, [12].into_iter(), now);
+
+        tracker.clear();
+
+        assert_eq!(0, tracker.get_pending_amount());
+        assert!(tracker.set_ok(1, 10).is_none());
+        assert!(tracker.set_ok(2, 12).is_none());
+    }
+}
@@ -10,6 +10,8 @@ pub struct PacketProtVer {
 pub struct ConnectionAttributes {
     pub versions: PacketVersions,
     pub protocol_version: i32,
+    //Rejects confirmation packets with unsorted, overlapping or inverted QueueIndexRanges
+    pub strict_queue_ranges: bool,
 }
 
 impl ConnectionAttributes {
@@ -17,6 +19,7 @@ impl ConnectionAttributes {
         Self {
             versions: PacketVersions::new(),
             protocol_version: protocol_version,
+            strict_queue_ranges: false,
         }
     }
 
@@ -29,4 +32,9 @@ impl ConnectionAttributes {
     pub fn get_packet_version(&self, packet_no: u8) -> i32 {
         self.versions.get_packet_version(packet_no)
     }
+
+    //Packets which appeared later than protocol version are sent only if other side declared them with PacketVersions
+    pub fn is_packet_supported(&self, packet_no: u8) -> bool {
+        self.versions.get_packet_version(packet_no) > 0
+    }
 }
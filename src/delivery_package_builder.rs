@@ -1,12 +1,16 @@
 use my_service_bus_abstractions::MyServiceBusMessage;
 
-use crate::{tcp_message_id, tcp_serializers::*, PacketProtVer, TcpContract};
+use crate::{
+    tcp_message_id, tcp_serializers::*, DeliveredMessageDetails, PacketProtVer, QueueLag,
+    TcpContract,
+};
 
 pub struct DeliverTcpPacketBuilder {
     payload: Vec<u8>,
     amount_offset: usize,
     version: PacketProtVer,
     amount: i32,
+    lag: Option<QueueLag>,
 }
 
 impl DeliverTcpPacketBuilder {
@@ -25,13 +29,29 @@ impl DeliverTcpPacketBuilder {
             amount_offset,
             version,
             amount: 0,
+            lag: None,
         }
     }
 
+    //Lag is written only if client supports NewMessages packet version 2+
+    pub fn set_lag(&mut self, lag: QueueLag) {
+        self.lag = Some(lag);
+    }
+
     pub fn append_packet(&mut self, msg: &impl MyServiceBusMessage) {
-        crate::tcp_serializers::messages_to_deliver::serialize(
+        self.append_packet_with_details(msg, DeliveredMessageDetails::default());
+    }
+
+    //Details are written only if client supports NewMessages packet version 3+
+    pub fn append_packet_with_details(
+        &mut self,
+        msg: &impl MyServiceBusMessage,
+        details: DeliveredMessageDetails,
+    ) {
+        crate::tcp_serializers::messages_to_deliver::serialize_with_details(
             &mut self.payload,
             msg,
+            &details,
             &self.version,
         );
 
@@ -42,6 +62,11 @@ impl DeliverTcpPacketBuilder {
         let size = self.amount.to_le_bytes();
         let dest = &mut self.payload[self.amount_offset..self.amount_offset + 4];
         dest.copy_from_slice(size.as_slice());
+
+        if self.version.packet_version >= 2 {
+            queue_lag::serialize(&mut self.payload, self.lag.as_ref());
+        }
+
         TcpContract::Raw(self.payload)
     }
 }
@@ -98,6 +123,7 @@ mod tests {
             queue_id,
             confirmation_id,
             mut messages,
+            ..
         } = result
         {
             assert_eq!("test_topic", topic_id);
@@ -105,13 +131,13 @@ mod tests {
             assert_eq!(15, confirmation_id);
             assert_eq!(2, messages.len());
 
-            let result_msg1 = messages.remove(0);
+            let result_msg1 = messages.remove(0).message;
 
             assert_eq!(1, result_msg1.attempt_no);
             assert_eq!(msg1.content, result_msg1.content);
             assert_eq!(true, result_msg1.headers.is_none());
 
-            let result_msg2 = messages.remove(0);
+            let result_msg2 = messages.remove(0).message;
 
             assert_eq!(2, result_msg2.attempt_no);
             assert_eq!(msg2.content, result_msg2.content);
@@ -163,6 +189,7 @@ mod tests {
             queue_id,
             confirmation_id,
             mut messages,
+            ..
         } = result
         {
             assert_eq!("test_topic", topic_id);
@@ -170,13 +197,13 @@ mod tests {
             assert_eq!(15, confirmation_id);
             assert_eq!(2, messages.len());
 
-            let result_msg1 = messages.remove(0);
+            let result_msg1 = messages.remove(0).message;
 
             assert_eq!(1, result_msg1.attempt_no);
             assert_eq!(msg1.content, result_msg1.content);
             assert_eq!(2, result_msg1.headers.unwrap().len());
 
-            let result_msg2 = messages.remove(0);
+            let result_msg2 = messages.remove(0).message;
 
             assert_eq!(2, result_msg2.attempt_no);
             assert_eq!(msg2.content, result_msg2.content);
@@ -185,4 +212,145 @@ mod tests {
             panic!("We should not be ere")
         }
     }
+
+    #[tokio::test]
+    async fn test_lag_v2() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let version = PacketProtVer {
+            packet_version: 2,
+            protocol_version: PROTOCOL_VERSION,
+        };
+
+        let msg = MySbMessage {
+            id: 1.into(),
+            content: vec![1, 1, 1],
+            headers: None,
+            attempt_no: 1,
+        };
+
+        let mut builder =
+            DeliverTcpPacketBuilder::new("test_topic", "test_queue", 15, version.clone());
+
+        builder.append_packet(&msg);
+        builder.set_lag(QueueLag {
+            remaining_messages: 99,
+            newest_message_id: Some(100),
+        });
+
+        let tcp_contract = builder.get_result();
+
+        let result = convert_from_raw(tcp_contract, &version).await;
+
+        if let TcpContract::NewMessages { messages, lag, .. } = result {
+            assert_eq!(1, messages.len());
+
+            let lag = lag.unwrap();
+            assert_eq!(99, lag.remaining_messages);
+            assert_eq!(Some(100), lag.newest_message_id);
+        } else {
+            panic!("We should not be ere")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_publish_timestamps_v3() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let version = PacketProtVer {
+            packet_version: 3,
+            protocol_version: PROTOCOL_VERSION,
+        };
+
+        let msg1 = MySbMessage {
+            id: 1.into(),
+            content: vec![1, 1, 1],
+            headers: None,
+            attempt_no: 1,
+        };
+
+        let msg2 = MySbMessage {
+            id: 2.into(),
+            content: vec![2, 2, 2],
+            headers: None,
+            attempt_no: 1,
+        };
+
+        let mut builder =
+            DeliverTcpPacketBuilder::new("test_topic", "test_queue", 15, version.clone());
+
+        builder.append_packet_with_details(
+            &msg1,
+            DeliveredMessageDetails {
+                published: 1_000_000,
+                expires: None,
+            },
+        );
+        builder.append_packet(&msg2);
+
+        let tcp_contract = builder.get_result();
+
+        let result = convert_from_raw(tcp_contract, &version).await;
+
+        if let TcpContract::NewMessages { messages, lag, .. } = result {
+            assert_eq!(2, messages.len());
+            assert!(lag.is_none());
+
+            assert_eq!(1, messages[0].message.id.get_value());
+            assert_eq!(1_000_000, messages[0].details.published);
+            assert_eq!(2, messages[1].message.id.get_value());
+            assert_eq!(0, messages[1].details.published);
+        } else {
+            panic!("We should not be ere")
+        }
+    }
+
+    #[tokio::test]
+    async fn test_expiration_v4() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let version = PacketProtVer {
+            packet_version: 4,
+            protocol_version: PROTOCOL_VERSION,
+        };
+
+        let msg = MySbMessage {
+            id: 1.into(),
+            content: vec![1, 1, 1],
+            headers: None,
+            attempt_no: 1,
+        };
+
+        let mut builder =
+            DeliverTcpPacketBuilder::new("test_topic", "test_queue", 15, version.clone());
+
+        builder.append_packet_with_details(
+            &msg,
+            DeliveredMessageDetails {
+                published: 1_000_000,
+                expires: Some(2_000_000),
+            },
+        );
+        builder.set_lag(QueueLag {
+            remaining_messages: 0,
+            newest_message_id: Some(1),
+        });
+
+        let tcp_contract = builder.get_result();
+
+        let result = convert_from_raw(tcp_contract, &version).await;
+
+        if let TcpContract::NewMessages { messages, lag, .. } = result {
+            assert_eq!(1, messages.len());
+            assert_eq!(Some(1), lag.unwrap().newest_message_id);
+
+            let details = &messages[0].details;
+            assert_eq!(1_000_000, details.published);
+            assert_eq!(Some(2_000_000), details.expires);
+            assert!(!details.is_expired(1_999_999));
+            assert!(details.is_expired(2_000_000));
+        } else {
+            panic!("We should not be ere")
+        }
+    }
 }
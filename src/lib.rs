@@ -1,16 +1,34 @@
 pub mod delivery_package_builder;
+pub mod queue_index_ranges;
 pub mod tcp_contract_to_string;
 pub mod tcp_message_id;
 pub mod tcp_serializers;
+pub mod topic_pattern;
 
+mod confirmation_tracker;
 mod connection_attrs;
+mod models;
 mod packet_versions;
+mod payload_error;
 
+mod publish_batcher;
+mod publish_requests_tracker;
+mod request_id_generator;
+mod tcp_contract_read_fail;
 mod tcp_contracts;
 mod tcp_serializer;
 
+pub use confirmation_tracker::ConfirmationTracker;
 pub use connection_attrs::{ConnectionAttributes, PacketProtVer};
 
+pub use models::*;
 pub use packet_versions::PacketVersions;
-pub use tcp_contracts::TcpContract;
+pub use payload_error::PayloadError;
+pub use publish_batcher::{PublishBatchPosition, PublishBatchSettings, PublishBatcher};
+pub use publish_requests_tracker::{
+    PublishRequestFail, PublishRequestResult, PublishRequestsTracker,
+};
+pub use request_id_generator::RequestIdGenerator;
+pub use tcp_contract_read_fail::TcpContractReadFail;
+pub use tcp_contracts::{ConfirmationId, RequestId, TcpContract};
 pub use tcp_serializer::MySbTcpSerializer;
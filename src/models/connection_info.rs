@@ -0,0 +1,12 @@
+use std::collections::HashMap;
+
+#[derive(Debug, Clone)]
+pub struct ConnectionInfo {
+    pub connection_id: i64,
+    //Name from Greeting packet
+    pub name: String,
+    pub protocol_version: i32,
+    pub ip: String,
+    //Packet version 1+. Versions declared by the connection with PacketVersions packet
+    pub packet_versions: HashMap<u8, i32>,
+}
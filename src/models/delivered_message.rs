@@ -0,0 +1,18 @@
+use my_service_bus_abstractions::MySbMessage;
+
+use super::DeliveredMessageDetails;
+
+#[derive(Debug, Clone)]
+pub struct DeliveredMessage {
+    pub message: MySbMessage,
+    pub details: DeliveredMessageDetails,
+}
+
+impl DeliveredMessage {
+    pub fn new(message: MySbMessage) -> Self {
+        Self {
+            message,
+            details: DeliveredMessageDetails::default(),
+        }
+    }
+}
@@ -0,0 +1,18 @@
+//Per message data which is not a part of MySbMessage. Delivered with NewMessages packet version 3+
+//by NewMessages, FetchMessagesResponse and PeekMessagesResponse
+#[derive(Debug, Clone, Default)]
+pub struct DeliveredMessageDetails {
+    //Unix microseconds. Assigned by the server when message is published. 0 - unknown
+    pub published: i64,
+    //Packet version 4+. Unix microseconds. None - message never expires
+    pub expires: Option<i64>,
+}
+
+impl DeliveredMessageDetails {
+    pub fn is_expired(&self, now: i64) -> bool {
+        match self.expires {
+            Some(expires) => now >= expires,
+            None => false,
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use std::time::Duration;
+
+use my_service_bus_abstractions::queue_with_intervals::QueueIndexRange;
+
+#[derive(Debug, Clone)]
+pub struct FailedMessages {
+    pub ranges: Vec<QueueIndexRange>,
+    pub reason_code: i32,
+    pub reason: String,
+    //Server keeps messages out of delivery at least for this time
+    pub redelivery_delay: Duration,
+    //Messages should not be redelivered anymore and have to be moved to dead letter queue
+    pub poison: bool,
+}
@@ -0,0 +1,114 @@
+use my_service_bus_abstractions::MyServiceBusMessage;
+
+//Filter is evaluated against message headers. Broker and client use the same evaluator
+#[derive(Debug, Clone, PartialEq)]
+pub enum HeaderFilter {
+    Equals { key: String, value: String },
+    In { key: String, values: Vec<String> },
+    Prefix { key: String, prefix: String },
+    And(Vec<HeaderFilter>),
+    Or(Vec<HeaderFilter>),
+}
+
+impl HeaderFilter {
+    pub fn is_match(&self, msg: &impl MyServiceBusMessage) -> bool {
+        let headers = msg.get_headers();
+
+        match self {
+            HeaderFilter::Equals { key, value } => match headers.and_then(|h| h.get(key)) {
+                Some(header_value) => header_value == value,
+                None => false,
+            },
+            HeaderFilter::In { key, values } => match headers.and_then(|h| h.get(key)) {
+                Some(header_value) => values.iter().any(|value| value == header_value),
+                None => false,
+            },
+            HeaderFilter::Prefix { key, prefix } => match headers.and_then(|h| h.get(key)) {
+                Some(header_value) => header_value.starts_with(prefix.as_str()),
+                None => false,
+            },
+            HeaderFilter::And(filters) => filters.iter().all(|filter| filter.is_match(msg)),
+            HeaderFilter::Or(filters) => filters.iter().any(|filter| filter.is_match(msg)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use my_service_bus_abstractions::MySbMessage;
+
+    use super::*;
+
+    fn create_message(headers: &[(&str, &str)]) -> MySbMessage {
+        let mut result = HashMap::new();
+
+        for (key, value) in headers {
+            result.insert(key.to_string(), value.to_string());
+        }
+
+        MySbMessage {
+            id: 1.into(),
+            content: vec![],
+            headers: Some(result),
+            attempt_no: 0,
+        }
+    }
+
+    #[test]
+    fn test_equals_and_prefix() {
+        let filter = HeaderFilter::And(vec![
+            HeaderFilter::Equals {
+                key: "type".to_string(),
+                value: "OrderCreated".to_string(),
+            },
+            HeaderFilter::Prefix {
+                key: "region".to_string(),
+                prefix: "eu-".to_string(),
+            },
+        ]);
+
+        assert!(filter.is_match(&create_message(&[
+            ("type", "OrderCreated"),
+            ("region", "eu-west")
+        ])));
+
+        assert!(!filter.is_match(&create_message(&[
+            ("type", "OrderCreated"),
+            ("region", "us-east")
+        ])));
+
+        assert!(!filter.is_match(&create_message(&[("type", "OrderCreated")])));
+    }
+
+    #[test]
+    fn test_in_and_or() {
+        let filter = HeaderFilter::Or(vec![
+            HeaderFilter::In {
+                key: "type".to_string(),
+                values: vec!["OrderCreated".to_string(), "OrderUpdated".to_string()],
+            },
+            HeaderFilter::Equals {
+                key: "priority".to_string(),
+                value: "high".to_string(),
+            },
+        ]);
+
+        assert!(filter.is_match(&create_message(&[("type", "OrderUpdated")])));
+        assert!(filter.is_match(&create_message(&[
+            ("type", "OrderDeleted"),
+            ("priority", "high")
+        ])));
+        assert!(!filter.is_match(&create_message(&[("type", "OrderDeleted")])));
+
+        let msg_without_headers = MySbMessage {
+            id: 1.into(),
+            content: vec![],
+            headers: None,
+            attempt_no: 0,
+        };
+
+        assert!(!filter.is_match(&msg_without_headers));
+    }
+}
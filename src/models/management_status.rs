@@ -0,0 +1,33 @@
+use crate::PayloadError;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ManagementStatus {
+    Ok,
+    TopicNotFound,
+    QueueNotFound,
+    QueueTypeMismatch,
+    Rejected,
+}
+
+impl ManagementStatus {
+    pub fn from_u8(src: u8) -> Result<Self, PayloadError> {
+        match src {
+            0 => Ok(ManagementStatus::Ok),
+            1 => Ok(ManagementStatus::TopicNotFound),
+            2 => Ok(ManagementStatus::QueueNotFound),
+            3 => Ok(ManagementStatus::QueueTypeMismatch),
+            4 => Ok(ManagementStatus::Rejected),
+            _ => Err(PayloadError::UnknownManagementStatus(src)),
+        }
+    }
+
+    pub fn into_u8(self) -> u8 {
+        match self {
+            ManagementStatus::Ok => 0,
+            ManagementStatus::TopicNotFound => 1,
+            ManagementStatus::QueueNotFound => 2,
+            ManagementStatus::QueueTypeMismatch => 3,
+            ManagementStatus::Rejected => 4,
+        }
+    }
+}
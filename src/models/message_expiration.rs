@@ -0,0 +1,21 @@
+use std::time::Duration;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MessageExpiration {
+    //Unix microseconds
+    At(i64),
+    //Counted by the server from the moment message is published
+    After(Duration),
+}
+
+impl MessageExpiration {
+    //Returns absolute expiration in unix microseconds
+    pub fn get_expires(&self, published: i64) -> i64 {
+        match self {
+            MessageExpiration::At(expires) => *expires,
+            MessageExpiration::After(ttl) => {
+                published.saturating_add(ttl.as_micros().min(i64::MAX as u128) as i64)
+            }
+        }
+    }
+}
@@ -0,0 +1,35 @@
+mod connection_info;
+mod delivered_message;
+mod delivered_message_details;
+mod failed_messages;
+mod header_filter;
+mod management_status;
+mod message_expiration;
+mod prefetch_limit;
+mod publish_message_options;
+mod publish_message_status;
+mod publish_messages;
+mod queue_info;
+mod queue_lag;
+mod queue_stats;
+mod subscribe_response_details;
+mod subscribe_start_position;
+mod topic_settings;
+
+pub use connection_info::ConnectionInfo;
+pub use delivered_message::DeliveredMessage;
+pub use delivered_message_details::DeliveredMessageDetails;
+pub use failed_messages::FailedMessages;
+pub use header_filter::HeaderFilter;
+pub use management_status::ManagementStatus;
+pub use message_expiration::MessageExpiration;
+pub use prefetch_limit::PrefetchLimit;
+pub use publish_message_options::{PublishMessageOptions, MAX_DEDUP_KEY_LEN};
+pub use publish_message_status::PublishMessageStatus;
+pub use publish_messages::PublishMessages;
+pub use queue_info::QueueInfo;
+pub use queue_lag::QueueLag;
+pub use queue_stats::QueueStats;
+pub use subscribe_response_details::{SubscribeResponseDetails, SubscribeStatus};
+pub use subscribe_start_position::SubscribeStartPosition;
+pub use topic_settings::{TopicPersistence, TopicSettings};
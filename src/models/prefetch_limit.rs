@@ -0,0 +1,7 @@
+//Server delivers messages to the subscriber only while unconfirmed messages and bytes fit the window.
+//Window starts with these values and is refilled by GrantCredits packets
+#[derive(Debug, Clone)]
+pub struct PrefetchLimit {
+    pub max_messages: i32,
+    pub max_bytes: i64,
+}
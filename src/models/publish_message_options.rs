@@ -0,0 +1,69 @@
+use crate::PayloadError;
+
+use super::MessageExpiration;
+
+//Dedup key is serialized as pascal string which has u8 length
+pub const MAX_DEDUP_KEY_LEN: usize = u8::MAX as usize;
+
+#[derive(Debug, Clone, Default)]
+pub struct PublishMessageOptions {
+    //Publish packet version 1+. None - batch expiration is used
+    pub expiration: Option<MessageExpiration>,
+    //Publish packet version 2+. Unix microseconds. Server holds the message until this moment
+    pub deliver_not_before: Option<i64>,
+    //Publish packet version 3+. Messages with the same key are published only once
+    pub dedup_key: Option<String>,
+}
+
+impl PublishMessageOptions {
+    pub fn is_default(&self) -> bool {
+        self.expiration.is_none() && self.deliver_not_before.is_none() && self.dedup_key.is_none()
+    }
+
+    //Checked by both sides. Message which expires before its due time can never be delivered.
+    //Batch expiration is applied to messages which do not have own expiration
+    pub fn validate(
+        &self,
+        batch_expiration: Option<&MessageExpiration>,
+    ) -> Result<(), PayloadError> {
+        if let Some(dedup_key) = &self.dedup_key {
+            if dedup_key.len() > MAX_DEDUP_KEY_LEN {
+                return Err(PayloadError::DedupKeyTooLong(dedup_key.len()));
+            }
+        }
+
+        let deliver_not_before = match self.deliver_not_before {
+            Some(deliver_not_before) => deliver_not_before,
+            None => return Ok(()),
+        };
+
+        if deliver_not_before < 0 {
+            return Err(PayloadError::NegativeDeliverNotBefore(deliver_not_before));
+        }
+
+        if let Some(MessageExpiration::At(expires)) = self.expiration.as_ref().or(batch_expiration)
+        {
+            if *expires <= deliver_not_before {
+                return Err(PayloadError::ExpiresBeforeDeliverNotBefore {
+                    expires: *expires,
+                    deliver_not_before,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    //Options which can not be encoded by the packet version are not dropped silently
+    pub fn check_packet_version(&self, packet_version: i32) -> Result<(), PayloadError> {
+        if packet_version < 2 && self.deliver_not_before.is_some() {
+            return Err(PayloadError::DeliverNotBeforeIsNotSupported(packet_version));
+        }
+
+        if packet_version < 3 && self.dedup_key.is_some() {
+            return Err(PayloadError::DedupKeyIsNotSupported(packet_version));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+use crate::PayloadError;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PublishMessageStatus {
+    Accepted,
+    //Message with the same dedup key is already published. Message is not published again
+    Duplicate,
+}
+
+impl PublishMessageStatus {
+    //Unknown status is not treated as Accepted - otherwise caller could lose the message silently
+    pub fn from_u8(src: u8) -> Result<Self, PayloadError> {
+        match src {
+            0 => Ok(PublishMessageStatus::Accepted),
+            1 => Ok(PublishMessageStatus::Duplicate),
+            _ => Err(PayloadError::UnknownPublishMessageStatus(src)),
+        }
+    }
+
+    pub fn into_u8(self) -> u8 {
+        match self {
+            PublishMessageStatus::Accepted => 0,
+            PublishMessageStatus::Duplicate => 1,
+        }
+    }
+}
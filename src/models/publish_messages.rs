@@ -0,0 +1,196 @@
+use my_service_bus_abstractions::publisher::MessageToPublish;
+
+use crate::PayloadError;
+
+use super::{MessageExpiration, PublishMessageOptions};
+
+//Messages of the Publish packet together with their options.
+//Options are checked when messages are created - so Publish packet can always be serialized
+#[derive(Debug, Clone)]
+pub struct PublishMessages {
+    messages: Vec<MessageToPublish>,
+    //Packet version 1+. Applied to messages which do not have own expiration
+    expiration: Option<MessageExpiration>,
+    //Packet version 1+. Same order as messages. Empty - default options for every message
+    options: Vec<PublishMessageOptions>,
+}
+
+impl PublishMessages {
+    pub fn new(messages: Vec<MessageToPublish>) -> Self {
+        Self::with_expiration(messages, None)
+    }
+
+    pub fn with_expiration(
+        messages: Vec<MessageToPublish>,
+        expiration: Option<MessageExpiration>,
+    ) -> Self {
+        Self {
+            messages,
+            expiration,
+            options: Vec::new(),
+        }
+    }
+
+    pub fn with_options(
+        messages: Vec<MessageToPublish>,
+        expiration: Option<MessageExpiration>,
+        options: Vec<PublishMessageOptions>,
+    ) -> Result<Self, PayloadError> {
+        crate::tcp_serializers::messages_to_publish::check_options_amount(
+            options.len() as i32,
+            messages.len(),
+        )?;
+
+        for item in &options {
+            item.validate(expiration.as_ref())?;
+        }
+
+        Ok(Self {
+            messages,
+            expiration,
+            options,
+        })
+    }
+
+    //Message with default options
+    pub fn push(&mut self, message: MessageToPublish) {
+        if !self.options.is_empty() {
+            self.options.push(PublishMessageOptions::default());
+        }
+
+        self.messages.push(message);
+    }
+
+    //Options are checked the same way with_options checks them.
+    //Options of previous messages are filled with defaults once first non default options are pushed
+    pub fn push_with_options(
+        &mut self,
+        message: MessageToPublish,
+        options: PublishMessageOptions,
+    ) -> Result<(), PayloadError> {
+        options.validate(self.expiration.as_ref())?;
+
+        if options.is_default() {
+            self.push(message);
+            return Ok(());
+        }
+
+        if self.options.is_empty() {
+            self.options
+                .resize(self.messages.len(), PublishMessageOptions::default());
+        }
+
+        self.messages.push(message);
+        self.options.push(options);
+
+        Ok(())
+    }
+
+    //Publish packet is not sent if peer can not decode some of the options
+    pub fn check_packet_version(&self, packet_version: i32) -> Result<(), PayloadError> {
+        for item in &self.options {
+            item.check_packet_version(packet_version)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_messages(&self) -> &[MessageToPublish] {
+        self.messages.as_slice()
+    }
+
+    pub fn get_expiration(&self) -> Option<&MessageExpiration> {
+        self.expiration.as_ref()
+    }
+
+    pub fn get_options(&self) -> &[PublishMessageOptions] {
+        self.options.as_slice()
+    }
+
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    pub fn into_messages(self) -> Vec<MessageToPublish> {
+        self.messages
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_message() -> MessageToPublish {
+        MessageToPublish {
+            headers: None,
+            content: vec![1, 2, 3],
+        }
+    }
+
+    #[test]
+    fn test_options_amount_is_checked() {
+        let result = PublishMessages::with_options(
+            vec![create_message(), create_message()],
+            None,
+            vec![PublishMessageOptions::default()],
+        );
+
+        assert_eq!(
+            Some(PayloadError::PublishOptionsAmountMismatch {
+                options_amount: 1,
+                messages_amount: 2,
+            }),
+            result.err()
+        );
+    }
+
+    #[test]
+    fn test_push_with_options() {
+        let mut messages = PublishMessages::new(Vec::new());
+
+        messages.push(create_message());
+        assert!(messages.get_options().is_empty());
+
+        messages
+            .push_with_options(create_message(), PublishMessageOptions::default())
+            .unwrap();
+        assert!(messages.get_options().is_empty());
+
+        messages
+            .push_with_options(
+                create_message(),
+                PublishMessageOptions {
+                    expiration: None,
+                    deliver_not_before: None,
+                    dedup_key: Some("order-15".to_string()),
+                },
+            )
+            .unwrap();
+        messages.push(create_message());
+
+        assert_eq!(4, messages.len());
+        assert_eq!(4, messages.get_options().len());
+        assert!(messages.get_options()[1].is_default());
+        assert_eq!(
+            Some("order-15".to_string()),
+            messages.get_options()[2].dedup_key
+        );
+        assert!(messages.get_options()[3].is_default());
+
+        let result = messages.push_with_options(
+            create_message(),
+            PublishMessageOptions {
+                expiration: Some(MessageExpiration::At(1)),
+                deliver_not_before: Some(2),
+                dedup_key: None,
+            },
+        );
+
+        assert!(result.is_err());
+        assert_eq!(4, messages.len());
+    }
+}
@@ -0,0 +1,7 @@
+use my_service_bus_abstractions::subscriber::TopicQueueType;
+
+#[derive(Debug, Clone)]
+pub struct QueueInfo {
+    pub queue_id: String,
+    pub queue_type: TopicQueueType,
+}
@@ -0,0 +1,7 @@
+#[derive(Debug, Clone)]
+pub struct QueueLag {
+    //Messages in the queue which are not delivered yet
+    pub remaining_messages: i64,
+    //None - topic has no messages yet
+    pub newest_message_id: Option<i64>,
+}
@@ -0,0 +1,12 @@
+#[derive(Debug, Clone)]
+pub struct QueueStats {
+    pub queue_id: String,
+    pub messages_amount: i64,
+    //None - queue is empty
+    pub first_message_id: Option<i64>,
+    pub last_message_id: Option<i64>,
+    //Messages which are delivered but not confirmed yet
+    pub in_flight_amount: i64,
+    pub subscribers_amount: i32,
+    pub publish_messages_per_second: i32,
+}
@@ -0,0 +1,42 @@
+use my_service_bus_abstractions::subscriber::TopicQueueType;
+
+use crate::PayloadError;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SubscribeStatus {
+    Ok,
+    TopicNotFound,
+    QueueTypeMismatch,
+    Rejected,
+}
+
+impl SubscribeStatus {
+    pub fn from_u8(src: u8) -> Result<Self, PayloadError> {
+        match src {
+            0 => Ok(SubscribeStatus::Ok),
+            1 => Ok(SubscribeStatus::TopicNotFound),
+            2 => Ok(SubscribeStatus::QueueTypeMismatch),
+            3 => Ok(SubscribeStatus::Rejected),
+            _ => Err(PayloadError::UnknownSubscribeStatus(src)),
+        }
+    }
+
+    pub fn into_u8(self) -> u8 {
+        match self {
+            SubscribeStatus::Ok => 0,
+            SubscribeStatus::TopicNotFound => 1,
+            SubscribeStatus::QueueTypeMismatch => 2,
+            SubscribeStatus::Rejected => 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SubscribeResponseDetails {
+    pub status: SubscribeStatus,
+    pub error: Option<String>,
+    //None - queue is empty
+    pub first_message_id: Option<i64>,
+    pub last_message_id: Option<i64>,
+    pub queue_type: TopicQueueType,
+}
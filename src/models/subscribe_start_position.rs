@@ -0,0 +1,10 @@
+//Where new queue starts. Existing queue keeps its position
+#[derive(Debug, Clone, PartialEq)]
+pub enum SubscribeStartPosition {
+    ServerDefault,
+    Earliest,
+    Latest,
+    FromMessageId(i64),
+    //Unix microseconds
+    FromTimestamp(i64),
+}
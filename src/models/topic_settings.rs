@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+use crate::PayloadError;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TopicPersistence {
+    //Messages are kept in memory only
+    InMemory,
+    Persistent,
+    //Publish is confirmed only after messages are persisted
+    PersistImmediately,
+}
+
+impl TopicPersistence {
+    pub fn from_u8(src: u8) -> Result<Self, PayloadError> {
+        match src {
+            0 => Ok(TopicPersistence::InMemory),
+            1 => Ok(TopicPersistence::Persistent),
+            2 => Ok(TopicPersistence::PersistImmediately),
+            _ => Err(PayloadError::UnknownTopicPersistence(src)),
+        }
+    }
+
+    pub fn into_u8(self) -> u8 {
+        match self {
+            TopicPersistence::InMemory => 0,
+            TopicPersistence::Persistent => 1,
+            TopicPersistence::PersistImmediately => 2,
+        }
+    }
+}
+
+//None values - server defaults are used
+#[derive(Debug, Clone)]
+pub struct TopicSettings {
+    pub max_messages_amount: Option<i64>,
+    pub persistence: TopicPersistence,
+    //Packet version 2+
+    pub max_bytes: Option<i64>,
+    pub message_ttl: Option<Duration>,
+    pub max_message_size: Option<i32>,
+}
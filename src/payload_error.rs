@@ -0,0 +1,35 @@
+use crate::queue_index_ranges::QueueIndexRangesError;
+
+//Packet id is known, but its payload violates the contract
+#[derive(Debug, Clone, PartialEq)]
+pub enum PayloadError {
+    InvalidQueueIndexRanges(QueueIndexRangesError),
+    InvalidHeaderFilter,
+    UnknownSubscribeStartPosition(u8),
+    UnknownMessageExpiration(u8),
+    NegativeLength(i32),
+    PublishOptionsAmountMismatch {
+        options_amount: i32,
+        messages_amount: usize,
+    },
+    UnknownPublishMessageStatus(u8),
+    NegativeDeliverNotBefore(i64),
+    ExpiresBeforeDeliverNotBefore {
+        expires: i64,
+        deliver_not_before: i64,
+    },
+    DedupKeyTooLong(usize),
+    NegativeDuration(i64),
+    UnknownTopicPersistence(u8),
+    UnknownManagementStatus(u8),
+    UnknownSubscribeStatus(u8),
+    //Packet version of the peer is lower than the one required by the option
+    DeliverNotBeforeIsNotSupported(i32),
+    DedupKeyIsNotSupported(i32),
+}
+
+impl From<QueueIndexRangesError> for PayloadError {
+    fn from(src: QueueIndexRangesError) -> Self {
+        PayloadError::InvalidQueueIndexRanges(src)
+    }
+}
@@ -0,0 +1,539 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use my_service_bus_abstractions::publisher::MessageToPublish;
+
+use crate::{
+    MessageExpiration, PayloadError, PublishMessageOptions, PublishMessageStatus, PublishMessages,
+    RequestId, RequestIdGenerator, TcpContract,
+};
+
+#[derive(Debug, Clone)]
+pub struct PublishBatchSettings {
+    pub max_messages: usize,
+    pub max_bytes: usize,
+    pub linger: Duration,
+    pub persist_immediately: bool,
+    //Batch expiration of every Publish packet. Applied to messages which do not have own expiration
+    pub expiration: Option<MessageExpiration>,
+}
+
+//Where the message is placed: Publish packet and position of the message inside of it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PublishBatchPosition {
+    pub request_id: RequestId,
+    pub index: usize,
+}
+
+impl PublishBatchPosition {
+    //Statuses of PublishDetailedResponse are in the same order as messages of the Publish packet
+    pub fn get_status(&self, statuses: &[PublishMessageStatus]) -> Option<PublishMessageStatus> {
+        statuses.get(self.index).copied()
+    }
+}
+
+struct PublishBatch {
+    request_id: RequestId,
+    //Publish packet carries no options at all if none of the messages has them
+    messages: PublishMessages,
+    size: usize,
+    created: Instant,
+}
+
+impl PublishBatch {
+    pub fn new(
+        request_id: RequestId,
+        expiration: Option<MessageExpiration>,
+        created: Instant,
+    ) -> Self {
+        Self {
+            request_id,
+            messages: PublishMessages::with_expiration(Vec::new(), expiration),
+            size: 0,
+            created,
+        }
+    }
+
+    fn get_next_position(&self) -> PublishBatchPosition {
+        PublishBatchPosition {
+            request_id: self.request_id,
+            index: self.messages.len(),
+        }
+    }
+}
+
+pub struct PublishBatcher {
+    settings: PublishBatchSettings,
+    batches: HashMap<String, PublishBatch>,
+    ready: Vec<TcpContract>,
+    request_ids: RequestIdGenerator,
+}
+
+impl PublishBatcher {
+    //request_ids has to be shared with PublishRequestsTracker of the same connection
+    pub fn new(settings: PublishBatchSettings, request_ids: RequestIdGenerator) -> Self {
+        Self {
+            settings,
+            batches: HashMap::new(),
+            ready: Vec::new(),
+            request_ids,
+        }
+    }
+
+    //Returns position of the message in the Publish packet which is going to carry it.
+    //Caller waits for PublishResponse or PublishDetailedResponse with the same request_id
+    pub fn add(
+        &mut self,
+        topic_id: &str,
+        message: MessageToPublish,
+        now: Instant,
+    ) -> PublishBatchPosition {
+        let batch = self.get_batch(topic_id, get_message_size(&message, None), now);
+
+        let result = batch.get_next_position();
+        batch.messages.push(message);
+
+        self.flush_if_full(topic_id);
+
+        result
+    }
+
+    //Options are checked the same way Publish packet checks them - so batch is never rejected because of one message
+    pub fn add_with_options(
+        &mut self,
+        topic_id: &str,
+        message: MessageToPublish,
+        options: PublishMessageOptions,
+        now: Instant,
+    ) -> Result<PublishBatchPosition, PayloadError> {
+        //Checked before the batch is touched - so rejected message does not flush it
+        options.validate(self.settings.expiration.as_ref())?;
+
+        let batch = self.get_batch(topic_id, get_message_size(&message, Some(&options)), now);
+
+        let result = batch.get_next_position();
+        batch.messages.push_with_options(message, options)?;
+
+        self.flush_if_full(topic_id);
+
+        Ok(result)
+    }
+
+    pub fn get_contracts_to_send(&mut self, now: Instant) -> Vec<TcpContract> {
+        let expired: Vec<String> = self
+            .batches
+            .iter()
+            .filter(|(_, batch)| now >= batch.created + self.settings.linger)
+            .map(|(topic_id, _)| topic_id.to_string())
+            .collect();
+
+        for topic_id in expired {
+            self.flush_topic(topic_id.as_str());
+        }
+
+        std::mem::take(&mut self.ready)
+    }
+
+    pub fn flush_all(&mut self) -> Vec<TcpContract> {
+        let topics: Vec<String> = self.batches.keys().cloned().collect();
+
+        for topic_id in topics {
+            self.flush_topic(topic_id.as_str());
+        }
+
+        std::mem::take(&mut self.ready)
+    }
+
+    pub fn get_next_deadline(&self) -> Option<Instant> {
+        self.batches
+            .values()
+            .map(|batch| batch.created + self.settings.linger)
+            .min()
+    }
+
+    pub fn has_pending(&self) -> bool {
+        !self.batches.is_empty() || !self.ready.is_empty()
+    }
+
+    //Batch which is going to carry the message of message_size. Batch is flushed first if message does not fit into it
+    fn get_batch(
+        &mut self,
+        topic_id: &str,
+        message_size: usize,
+        now: Instant,
+    ) -> &mut PublishBatch {
+        if let Some(batch) = self.batches.get(topic_id) {
+            if batch.size + message_size > self.settings.max_bytes {
+                self.flush_topic(topic_id);
+            }
+        }
+
+        if !self.batches.contains_key(topic_id) {
+            let request_id = self.request_ids.get_next();
+            let batch = PublishBatch::new(request_id, self.settings.expiration.clone(), now);
+            self.batches.insert(topic_id.to_string(), batch);
+        }
+
+        let batch = self.batches.get_mut(topic_id).unwrap();
+        batch.size += message_size;
+        batch
+    }
+
+    fn flush_if_full(&mut self, topic_id: &str) {
+        if let Some(batch) = self.batches.get(topic_id) {
+            if batch.messages.len() >= self.settings.max_messages
+                || batch.size >= self.settings.max_bytes
+            {
+                self.flush_topic(topic_id);
+            }
+        }
+    }
+
+    fn flush_topic(&mut self, topic_id: &str) {
+        if let Some((topic_id, batch)) = self.batches.remove_entry(topic_id) {
+            self.ready.push(TcpContract::Publish {
+                topic_id,
+                request_id: batch.request_id,
+                persist_immediately: self.settings.persist_immediately,
+                data_to_publish: batch.messages,
+            });
+        }
+    }
+}
+
+fn get_message_size(message: &MessageToPublish, options: Option<&PublishMessageOptions>) -> usize {
+    //i32 content len + headers amount byte
+    let mut result = message.content.len() + 5;
+
+    if let Some(headers) = &message.headers {
+        for (key, value) in headers {
+            result += key.len() + value.len() + 2;
+        }
+    }
+
+    if let Some(dedup_key) = options.and_then(|options| options.dedup_key.as_ref()) {
+        result += dedup_key.len() + 1;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_settings() -> PublishBatchSettings {
+        PublishBatchSettings {
+            max_messages: 3,
+            max_bytes: 1024,
+            linger: Duration::from_millis(100),
+            persist_immediately: false,
+            expiration: None,
+        }
+    }
+
+    fn create_message(size: usize) -> MessageToPublish {
+        MessageToPublish {
+            headers: None,
+            content: vec![0u8; size],
+        }
+    }
+
+    #[test]
+    fn test_flush_by_messages_amount() {
+        let mut batcher = PublishBatcher::new(create_settings(), RequestIdGenerator::new());
+        let now = Instant::now();
+
+        let position_1 = batcher.add("topic", create_message(10), now);
+        let position_2 = batcher.add("topic", create_message(10), now);
+        let position_3 = batcher.add("topic", create_message(10), now);
+        let position_4 = batcher.add("topic", create_message(10), now);
+
+        assert_eq!(position_1.request_id, position_2.request_id);
+        assert_eq!(position_1.request_id, position_3.request_id);
+        assert_ne!(position_1.request_id, position_4.request_id);
+
+        assert_eq!(0, position_1.index);
+        assert_eq!(1, position_2.index);
+        assert_eq!(2, position_3.index);
+        assert_eq!(0, position_4.index);
+
+        let contracts = batcher.get_contracts_to_send(now);
+
+        assert_eq!(1, contracts.len());
+
+        match &contracts[0] {
+            TcpContract::Publish {
+                topic_id,
+                request_id,
+                data_to_publish,
+                ..
+            } => {
+                assert_eq!("topic", topic_id);
+                assert_eq!(position_1.request_id, *request_id);
+                assert_eq!(3, data_to_publish.len());
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+
+        assert!(batcher.has_pending());
+    }
+
+    #[test]
+    fn test_flush_by_size() {
+        let mut batcher = PublishBatcher::new(create_settings(), RequestIdGenerator::new());
+        let now = Instant::now();
+
+        let position_1 = batcher.add("topic", create_message(600), now);
+        let position_2 = batcher.add("topic", create_message(600), now);
+
+        assert_ne!(position_1.request_id, position_2.request_id);
+
+        let contracts = batcher.get_contracts_to_send(now);
+        assert_eq!(1, contracts.len());
+
+        let contracts = batcher.flush_all();
+        assert_eq!(1, contracts.len());
+
+        assert!(!batcher.has_pending());
+    }
+
+    #[test]
+    fn test_flush_by_linger() {
+        let mut batcher = PublishBatcher::new(create_settings(), RequestIdGenerator::new());
+        let now = Instant::now();
+
+        batcher.add("topic1", create_message(10), now);
+        batcher.add(
+            "topic2",
+            create_message(10),
+            now + Duration::from_millis(50),
+        );
+
+        assert_eq!(
+            Some(now + Duration::from_millis(100)),
+            batcher.get_next_deadline()
+        );
+
+        let contracts = batcher.get_contracts_to_send(now + Duration::from_millis(99));
+        assert_eq!(0, contracts.len());
+
+        let contracts = batcher.get_contracts_to_send(now + Duration::from_millis(100));
+        assert_eq!(1, contracts.len());
+
+        let contracts = batcher.get_contracts_to_send(now + Duration::from_millis(150));
+        assert_eq!(1, contracts.len());
+
+        assert!(!batcher.has_pending());
+    }
+
+    #[tokio::test]
+    async fn test_options_and_expiration() {
+        let mut settings = create_settings();
+        settings.expiration = Some(MessageExpiration::After(Duration::from_secs(60)));
+
+        let mut batcher = PublishBatcher::new(settings, RequestIdGenerator::new());
+        let now = Instant::now();
+
+        batcher.add("topic1", create_message(10), now);
+
+        let position = batcher
+            .add_with_options(
+                "topic2",
+                create_message(10),
+                PublishMessageOptions {
+                    expiration: None,
+                    deliver_not_before: None,
+                    dedup_key: Some("order-15".to_string()),
+                },
+                now,
+            )
+            .unwrap();
+        assert_eq!(
+            position.request_id,
+            batcher.add("topic2", create_message(10), now).request_id
+        );
+
+        let result = batcher.add_with_options(
+            "topic2",
+            create_message(10),
+            PublishMessageOptions {
+                expiration: Some(MessageExpiration::At(1)),
+                deliver_not_before: Some(2),
+                dedup_key: None,
+            },
+            now,
+        );
+        assert!(result.is_err());
+
+        let mut contracts = batcher.flush_all();
+        contracts.sort_by_key(|contract| match contract {
+            TcpContract::Publish { topic_id, .. } => topic_id.to_string(),
+            _ => String::new(),
+        });
+
+        assert_eq!(2, contracts.len());
+
+        match &contracts[0] {
+            TcpContract::Publish {
+                data_to_publish, ..
+            } => {
+                assert_eq!(
+                    Some(&MessageExpiration::After(Duration::from_secs(60))),
+                    data_to_publish.get_expiration()
+                );
+                assert!(data_to_publish.get_options().is_empty());
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+
+        match &contracts[1] {
+            TcpContract::Publish {
+                data_to_publish, ..
+            } => {
+                let options = data_to_publish.get_options();
+                assert_eq!(2, data_to_publish.len());
+                assert_eq!(2, options.len());
+                assert_eq!(Some("order-15".to_string()), options[0].dedup_key);
+                assert!(options[1].dedup_key.is_none());
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+
+        let mut attr = crate::ConnectionAttributes::new(3);
+        attr.versions
+            .set_packet_version(crate::tcp_message_id::PUBLISH, 3);
+
+        let serialized_data = contracts.remove(1).serialize_with_attr(&attr);
+        let mut socket_reader =
+            my_tcp_sockets::socket_reader::SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::Publish {
+                data_to_publish, ..
+            } => {
+                assert_eq!(
+                    Some(&MessageExpiration::After(Duration::from_secs(60))),
+                    data_to_publish.get_expiration()
+                );
+                assert_eq!(
+                    Some("order-15".to_string()),
+                    data_to_publish.get_options()[0].dedup_key
+                );
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_options_are_checked_against_batch_expiration() {
+        let mut settings = create_settings();
+        settings.expiration = Some(MessageExpiration::At(1_000_000));
+
+        let mut batcher = PublishBatcher::new(settings, RequestIdGenerator::new());
+        let now = Instant::now();
+
+        let result = batcher.add_with_options(
+            "topic",
+            create_message(10),
+            PublishMessageOptions {
+                expiration: None,
+                deliver_not_before: Some(1_000_000),
+                dedup_key: None,
+            },
+            now,
+        );
+        assert!(result.is_err());
+
+        let result = batcher.add_with_options(
+            "topic",
+            create_message(10),
+            PublishMessageOptions {
+                expiration: Some(MessageExpiration::At(2_000_000)),
+                deliver_not_before: Some(1_000_000),
+                dedup_key: None,
+            },
+            now,
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_request_ids_are_shared_with_tracker() {
+        let request_ids = RequestIdGenerator::new();
+
+        let mut batcher = PublishBatcher::new(create_settings(), request_ids.clone());
+        let mut tracker = crate::PublishRequestsTracker::new(Duration::from_secs(10), request_ids);
+        let now = Instant::now();
+
+        let (tracker_request_id, _receiver) = tracker.new_request(now);
+        let batch_request_id = batcher.add("topic", create_message(10), now).request_id;
+
+        assert_ne!(tracker_request_id, batch_request_id);
+
+        let _receiver = tracker.subscribe(batch_request_id, now);
+        let (next_request_id, _receiver) = tracker.new_request(now);
+
+        assert!(next_request_id > batch_request_id);
+    }
+
+    #[tokio::test]
+    async fn test_statuses_are_mapped_to_messages() {
+        let request_ids = RequestIdGenerator::new();
+
+        let mut batcher = PublishBatcher::new(create_settings(), request_ids.clone());
+        let mut tracker = crate::PublishRequestsTracker::new(Duration::from_secs(10), request_ids);
+        let now = Instant::now();
+
+        let position_1 = batcher
+            .add_with_options(
+                "topic",
+                create_message(10),
+                PublishMessageOptions {
+                    expiration: None,
+                    deliver_not_before: None,
+                    dedup_key: Some("order-15".to_string()),
+                },
+                now,
+            )
+            .unwrap();
+        let position_2 = batcher.add("topic", create_message(10), now);
+
+        let receiver_1 = tracker.subscribe(position_1.request_id, now);
+        let receiver_2 = tracker.subscribe(position_2.request_id, now);
+
+        tracker.handle_contract(&TcpContract::PublishDetailedResponse {
+            request_id: position_1.request_id,
+            statuses: vec![
+                PublishMessageStatus::Duplicate,
+                PublishMessageStatus::Accepted,
+            ],
+        });
+
+        let statuses = receiver_1.await.unwrap().unwrap().unwrap();
+        assert_eq!(
+            Some(PublishMessageStatus::Duplicate),
+            position_1.get_status(&statuses)
+        );
+
+        let statuses = receiver_2.await.unwrap().unwrap().unwrap();
+        assert_eq!(
+            Some(PublishMessageStatus::Accepted),
+            position_2.get_status(&statuses)
+        );
+    }
+}
@@ -0,0 +1,269 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use tokio::sync::oneshot;
+
+use crate::{PublishMessageStatus, RequestId, RequestIdGenerator, TcpContract};
+
+#[derive(Debug, Clone)]
+pub enum PublishRequestFail {
+    Rejected(String),
+    Disconnected,
+    Timeout,
+}
+
+//Statuses are provided if server replied with PublishDetailedResponse. Same order as messages of the Publish packet
+pub type PublishRequestResult = Result<Option<Vec<PublishMessageStatus>>, PublishRequestFail>;
+
+struct PendingPublishRequest {
+    created: Instant,
+    awaiters: Vec<oneshot::Sender<PublishRequestResult>>,
+}
+
+impl PendingPublishRequest {
+    fn complete(self, result: PublishRequestResult) {
+        for awaiter in self.awaiters {
+            //Receiver could be dropped if caller does not wait for the result anymore
+            let _ = awaiter.send(result.clone());
+        }
+    }
+}
+
+pub struct PublishRequestsTracker {
+    request_ids: RequestIdGenerator,
+    requests: HashMap<RequestId, PendingPublishRequest>,
+    timeout: Duration,
+}
+
+impl PublishRequestsTracker {
+    //request_ids has to be shared with PublishBatcher if both are used on the same connection
+    pub fn new(timeout: Duration, request_ids: RequestIdGenerator) -> Self {
+        Self {
+            request_ids,
+            requests: HashMap::new(),
+            timeout,
+        }
+    }
+
+    pub fn get_next_request_id(&mut self) -> RequestId {
+        self.request_ids.get_next()
+    }
+
+    pub fn new_request(
+        &mut self,
+        now: Instant,
+    ) -> (RequestId, oneshot::Receiver<PublishRequestResult>) {
+        let request_id = self.get_next_request_id();
+        let receiver = self.subscribe(request_id, now);
+        (request_id, receiver)
+    }
+
+    //Registers one more awaiter for the request. Used when several callers share the same Publish packet.
+    //Request ids allocated outside of the tracker are accepted as well and keep request ids monotonic
+    pub fn subscribe(
+        &mut self,
+        request_id: RequestId,
+        now: Instant,
+    ) -> oneshot::Receiver<PublishRequestResult> {
+        self.request_ids.reserve(request_id);
+
+        let (sender, receiver) = oneshot::channel();
+
+        self.requests
+            .entry(request_id)
+            .or_insert_with(|| PendingPublishRequest {
+                created: now,
+                awaiters: Vec::new(),
+            })
+            .awaiters
+            .push(sender);
+
+        receiver
+    }
+
+    //Returns true if contract is handled by the tracker and caller does not need it anymore.
+    //Reject fails all the pending requests, but it's still returned as not handled - it's a connection level event
+    pub fn handle_contract(&mut self, contract: &TcpContract) -> bool {
+        match contract {
+            TcpContract::PublishResponse { request_id } => {
+                self.confirm(*request_id);
+                true
+            }
+            //Duplicates are already published by previous attempt - so request is completed as well
+            TcpContract::PublishDetailedResponse {
+                request_id,
+                statuses,
+            } => {
+                self.confirm_with_statuses(*request_id, statuses.clone());
+                true
+            }
+            TcpContract::Reject { message } => {
+                self.fail_all(PublishRequestFail::Rejected(message.to_string()));
+                false
+            }
+            _ => false,
+        }
+    }
+
+    pub fn confirm(&mut self, request_id: RequestId) -> bool {
+        self.complete(request_id, Ok(None))
+    }
+
+    pub fn confirm_with_statuses(
+        &mut self,
+        request_id: RequestId,
+        statuses: Vec<PublishMessageStatus>,
+    ) -> bool {
+        self.complete(request_id, Ok(Some(statuses)))
+    }
+
+    pub fn disconnected(&mut self) {
+        self.fail_all(PublishRequestFail::Disconnected);
+    }
+
+    pub fn remove_expired(&mut self, now: Instant) -> Vec<RequestId> {
+        let expired: Vec<RequestId> = self
+            .requests
+            .iter()
+            .filter(|(_, request)| now >= request.created + self.timeout)
+            .map(|(request_id, _)| *request_id)
+            .collect();
+
+        for request_id in &expired {
+            if let Some(request) = self.requests.remove(request_id) {
+                request.complete(Err(PublishRequestFail::Timeout));
+            }
+        }
+
+        expired
+    }
+
+    pub fn get_pending_amount(&self) -> usize {
+        self.requests.len()
+    }
+
+    fn complete(&mut self, request_id: RequestId, result: PublishRequestResult) -> bool {
+        match self.requests.remove(&request_id) {
+            Some(request) => {
+                request.complete(result);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn fail_all(&mut self, fail: PublishRequestFail) {
+        for (_, request) in self.requests.drain() {
+            request.complete(Err(fail.clone()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_publish_response_completes_request() {
+        let mut tracker =
+            PublishRequestsTracker::new(Duration::from_secs(10), RequestIdGenerator::new());
+        let now = Instant::now();
+
+        let (request_id_1, receiver_1) = tracker.new_request(now);
+        let (request_id_2, receiver_2) = tracker.new_request(now);
+
+        assert_eq!(request_id_1 + 1, request_id_2);
+
+        let handled = tracker.handle_contract(&TcpContract::PublishResponse {
+            request_id: request_id_1,
+        });
+
+        assert!(handled);
+        assert!(receiver_1.await.unwrap().unwrap().is_none());
+        assert_eq!(1, tracker.get_pending_amount());
+
+        tracker.disconnected();
+
+        match receiver_2.await.unwrap() {
+            Err(PublishRequestFail::Disconnected) => {}
+            _ => {
+                panic!("Request should be failed as Disconnected");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_shared_request_id() {
+        let mut tracker =
+            PublishRequestsTracker::new(Duration::from_secs(10), RequestIdGenerator::new());
+        let now = Instant::now();
+
+        let receiver_1 = tracker.subscribe(5, now);
+        let receiver_2 = tracker.subscribe(5, now);
+
+        assert_eq!(6, tracker.get_next_request_id());
+
+        let handled = tracker.handle_contract(&TcpContract::Reject {
+            message: "Topic not found".to_string(),
+        });
+
+        assert!(!handled);
+        assert_eq!(0, tracker.get_pending_amount());
+
+        for receiver in [receiver_1, receiver_2] {
+            match receiver.await.unwrap() {
+                Err(PublishRequestFail::Rejected(message)) => {
+                    assert_eq!("Topic not found", message);
+                }
+                _ => {
+                    panic!("Request should be failed as Rejected");
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_publish_detailed_response_completes_request() {
+        let mut tracker =
+            PublishRequestsTracker::new(Duration::from_secs(10), RequestIdGenerator::new());
+        let now = Instant::now();
+
+        let (request_id, receiver) = tracker.new_request(now);
+
+        let handled = tracker.handle_contract(&TcpContract::PublishDetailedResponse {
+            request_id,
+            statuses: vec![crate::PublishMessageStatus::Duplicate],
+        });
+
+        assert!(handled);
+        assert_eq!(
+            Some(vec![crate::PublishMessageStatus::Duplicate]),
+            receiver.await.unwrap().unwrap()
+        );
+        assert_eq!(0, tracker.get_pending_amount());
+    }
+
+    #[tokio::test]
+    async fn test_timeout() {
+        let mut tracker =
+            PublishRequestsTracker::new(Duration::from_secs(10), RequestIdGenerator::new());
+        let now = Instant::now();
+
+        let (request_id_1, receiver_1) = tracker.new_request(now);
+        let (_, _receiver_2) = tracker.new_request(now + Duration::from_secs(5));
+
+        let expired = tracker.remove_expired(now + Duration::from_secs(10));
+
+        assert_eq!(vec![request_id_1], expired);
+        assert_eq!(1, tracker.get_pending_amount());
+
+        match receiver_1.await.unwrap() {
+            Err(PublishRequestFail::Timeout) => {}
+            _ => {
+                panic!("Request should be failed as Timeout");
+            }
+        }
+    }
+}
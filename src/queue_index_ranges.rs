@@ -0,0 +1,302 @@
+use my_service_bus_abstractions::queue_with_intervals::QueueIndexRange;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueueIndexRangesError {
+    InvalidRange { from_id: i64, to_id: i64 },
+    NotSortedOrOverlapping { prev_to_id: i64, from_id: i64 },
+}
+
+//Checks that ranges are valid, sorted and do not overlap each other
+pub fn validate(ranges: &[QueueIndexRange]) -> Result<(), QueueIndexRangesError> {
+    let mut prev_to_id: Option<i64> = None;
+
+    for range in ranges {
+        if range.from_id > range.to_id {
+            return Err(QueueIndexRangesError::InvalidRange {
+                from_id: range.from_id,
+                to_id: range.to_id,
+            });
+        }
+
+        if let Some(prev_to_id) = prev_to_id {
+            if range.from_id <= prev_to_id {
+                return Err(QueueIndexRangesError::NotSortedOrOverlapping {
+                    prev_to_id,
+                    from_id: range.from_id,
+                });
+            }
+        }
+
+        prev_to_id = Some(range.to_id);
+    }
+
+    Ok(())
+}
+
+//Builds minimal list of ranges. Ids can be unsorted and can contain duplicates
+pub fn from_ids(ids: impl IntoIterator<Item = i64>) -> Vec<QueueIndexRange> {
+    let mut ids: Vec<i64> = ids.into_iter().collect();
+    ids.sort_unstable();
+    ids.dedup();
+
+    let mut result: Vec<QueueIndexRange> = Vec::new();
+
+    for id in ids {
+        if let Some(last) = result.last_mut() {
+            if last.to_id.checked_add(1) == Some(id) {
+                last.to_id = id;
+                continue;
+            }
+        }
+
+        result.push(QueueIndexRange {
+            from_id: id,
+            to_id: id,
+        });
+    }
+
+    result
+}
+
+//Sorts ranges and merges overlapping and adjacent ones. Ranges with from_id > to_id are treated as empty
+pub fn normalize(ranges: &[QueueIndexRange]) -> Vec<QueueIndexRange> {
+    let mut src: Vec<&QueueIndexRange> = ranges
+        .iter()
+        .filter(|range| range.from_id <= range.to_id)
+        .collect();
+
+    src.sort_by_key(|range| range.from_id);
+
+    let mut result: Vec<QueueIndexRange> = Vec::with_capacity(src.len());
+
+    for range in src {
+        if let Some(last) = result.last_mut() {
+            if range.from_id <= last.to_id.saturating_add(1) {
+                if range.to_id > last.to_id {
+                    last.to_id = range.to_id;
+                }
+                continue;
+            }
+        }
+
+        result.push(QueueIndexRange {
+            from_id: range.from_id,
+            to_id: range.to_id,
+        });
+    }
+
+    result
+}
+
+pub fn merge(a: &[QueueIndexRange], b: &[QueueIndexRange]) -> Vec<QueueIndexRange> {
+    let mut src: Vec<QueueIndexRange> = Vec::with_capacity(a.len() + b.len());
+    src.extend_from_slice(a);
+    src.extend_from_slice(b);
+    normalize(&src)
+}
+
+pub fn intersect(a: &[QueueIndexRange], b: &[QueueIndexRange]) -> Vec<QueueIndexRange> {
+    let a = normalize(a);
+    let b = normalize(b);
+
+    let mut result = Vec::new();
+
+    let mut a_index = 0;
+    let mut b_index = 0;
+
+    while a_index < a.len() && b_index < b.len() {
+        let a_range = &a[a_index];
+        let b_range = &b[b_index];
+
+        let from_id = a_range.from_id.max(b_range.from_id);
+        let to_id = a_range.to_id.min(b_range.to_id);
+
+        if from_id <= to_id {
+            result.push(QueueIndexRange { from_id, to_id });
+        }
+
+        if a_range.to_id < b_range.to_id {
+            a_index += 1;
+        } else {
+            b_index += 1;
+        }
+    }
+
+    result
+}
+
+//Returns ids which are in a but not in b
+pub fn subtract(a: &[QueueIndexRange], b: &[QueueIndexRange]) -> Vec<QueueIndexRange> {
+    let a = normalize(a);
+    let b = normalize(b);
+
+    let mut result = Vec::new();
+
+    let mut b_index = 0;
+
+    for a_range in a {
+        while b_index < b.len() && b[b_index].to_id < a_range.from_id {
+            b_index += 1;
+        }
+
+        let mut from_id = a_range.from_id;
+        let mut covered_till_end = false;
+
+        let mut index = b_index;
+
+        while index < b.len() && b[index].from_id <= a_range.to_id {
+            let b_range = &b[index];
+
+            if b_range.from_id > from_id {
+                result.push(QueueIndexRange {
+                    from_id,
+                    to_id: b_range.from_id - 1,
+                });
+            }
+
+            if b_range.to_id >= a_range.to_id {
+                covered_till_end = true;
+                break;
+            }
+
+            //b_range.to_id < a_range.to_id here - so it can not overflow
+            from_id = b_range.to_id.saturating_add(1);
+            index += 1;
+        }
+
+        if !covered_till_end {
+            result.push(QueueIndexRange {
+                from_id,
+                to_id: a_range.to_id,
+            });
+        }
+    }
+
+    result
+}
+
+pub fn iterate_ids(ranges: &[QueueIndexRange]) -> impl Iterator<Item = i64> + '_ {
+    ranges.iter().flat_map(|range| range.from_id..=range.to_id)
+}
+
+pub fn get_ids_amount(ranges: &[QueueIndexRange]) -> i64 {
+    ranges
+        .iter()
+        .filter(|range| range.from_id <= range.to_id)
+        .map(|range| range.to_id.saturating_sub(range.from_id).saturating_add(1))
+        .fold(0i64, |acc, amount| acc.saturating_add(amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_tuples(ranges: &[QueueIndexRange]) -> Vec<(i64, i64)> {
+        ranges
+            .iter()
+            .map(|range| (range.from_id, range.to_id))
+            .collect()
+    }
+
+    fn from_tuples(src: &[(i64, i64)]) -> Vec<QueueIndexRange> {
+        src.iter()
+            .map(|(from_id, to_id)| QueueIndexRange {
+                from_id: *from_id,
+                to_id: *to_id,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_from_ids() {
+        let result = from_ids([5, 1, 2, 3, 7, 6, 2, 10]);
+        assert_eq!(vec![(1, 3), (5, 7), (10, 10)], to_tuples(&result));
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(validate(&from_tuples(&[(1, 3), (5, 7)])).is_ok());
+
+        assert_eq!(
+            Err(QueueIndexRangesError::InvalidRange {
+                from_id: 3,
+                to_id: 1
+            }),
+            validate(&from_tuples(&[(3, 1)]))
+        );
+
+        assert_eq!(
+            Err(QueueIndexRangesError::NotSortedOrOverlapping {
+                prev_to_id: 7,
+                from_id: 7
+            }),
+            validate(&from_tuples(&[(5, 7), (7, 9)]))
+        );
+    }
+
+    #[test]
+    fn test_normalize_and_merge() {
+        let result = normalize(&from_tuples(&[(5, 7), (1, 3), (4, 4), (10, 8)]));
+        assert_eq!(vec![(1, 7)], to_tuples(&result));
+
+        let result = merge(
+            &from_tuples(&[(1, 3), (10, 12)]),
+            &from_tuples(&[(2, 5), (20, 20)]),
+        );
+        assert_eq!(vec![(1, 5), (10, 12), (20, 20)], to_tuples(&result));
+    }
+
+    #[test]
+    fn test_intersect() {
+        let result = intersect(
+            &from_tuples(&[(1, 10), (20, 30)]),
+            &from_tuples(&[(5, 25), (28, 40)]),
+        );
+        assert_eq!(vec![(5, 10), (20, 25), (28, 30)], to_tuples(&result));
+    }
+
+    #[test]
+    fn test_subtract() {
+        let result = subtract(
+            &from_tuples(&[(1, 10), (20, 30)]),
+            &from_tuples(&[(3, 4), (8, 22), (30, 35)]),
+        );
+        assert_eq!(vec![(1, 2), (5, 7), (23, 29)], to_tuples(&result));
+    }
+
+    #[test]
+    fn test_max_id_does_not_overflow() {
+        let result = from_ids([i64::MAX - 1, i64::MAX, 5]);
+        assert_eq!(vec![(5, 5), (i64::MAX - 1, i64::MAX)], to_tuples(&result));
+
+        let result = subtract(
+            &from_tuples(&[(i64::MAX - 10, i64::MAX)]),
+            &from_tuples(&[(i64::MAX - 5, i64::MAX)]),
+        );
+        assert_eq!(vec![(i64::MAX - 10, i64::MAX - 6)], to_tuples(&result));
+
+        let result = subtract(
+            &from_tuples(&[(i64::MAX - 10, i64::MAX)]),
+            &from_tuples(&[(i64::MAX - 5, i64::MAX - 3)]),
+        );
+        assert_eq!(
+            vec![(i64::MAX - 10, i64::MAX - 6), (i64::MAX - 2, i64::MAX)],
+            to_tuples(&result)
+        );
+
+        assert_eq!(
+            i64::MAX,
+            get_ids_amount(&from_tuples(&[(i64::MIN, i64::MAX)]))
+        );
+    }
+
+    #[test]
+    fn test_iterate_ids() {
+        let ranges = from_tuples(&[(1, 3), (7, 8)]);
+
+        let ids: Vec<i64> = iterate_ids(&ranges).collect();
+
+        assert_eq!(vec![1, 2, 3, 7, 8], ids);
+        assert_eq!(5, get_ids_amount(&ranges));
+    }
+}
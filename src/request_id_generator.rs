@@ -0,0 +1,49 @@
+use std::sync::{
+    atomic::{AtomicI64, Ordering},
+    Arc,
+};
+
+use crate::RequestId;
+
+//Shared by everything which allocates request ids of the same connection,
+//so PublishBatcher and PublishRequestsTracker never give the same id to different Publish packets
+#[derive(Debug, Clone, Default)]
+pub struct RequestIdGenerator {
+    next_request_id: Arc<AtomicI64>,
+}
+
+impl RequestIdGenerator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get_next(&self) -> RequestId {
+        self.next_request_id.fetch_add(1, Ordering::SeqCst)
+    }
+
+    //Request id allocated somewhere else is never given out again
+    pub fn reserve(&self, request_id: RequestId) {
+        self.next_request_id
+            .fetch_max(request_id.saturating_add(1), Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shared_between_clones() {
+        let request_ids = RequestIdGenerator::new();
+        let cloned = request_ids.clone();
+
+        assert_eq!(0, request_ids.get_next());
+        assert_eq!(1, cloned.get_next());
+
+        cloned.reserve(10);
+        assert_eq!(11, request_ids.get_next());
+
+        request_ids.reserve(5);
+        assert_eq!(12, cloned.get_next());
+    }
+}
@@ -0,0 +1,34 @@
+use my_tcp_sockets::socket_reader::ReadingTcpContractFail;
+
+use crate::PayloadError;
+
+#[derive(Debug)]
+pub enum TcpContractReadFail {
+    //Packet can not be read: socket is disconnected, packet id is unknown or payload is truncated
+    Socket(ReadingTcpContractFail),
+    //Packet is read completely, but its payload violates the contract
+    InvalidPayload(PayloadError),
+}
+
+impl From<ReadingTcpContractFail> for TcpContractReadFail {
+    fn from(src: ReadingTcpContractFail) -> Self {
+        TcpContractReadFail::Socket(src)
+    }
+}
+
+impl From<PayloadError> for TcpContractReadFail {
+    fn from(src: PayloadError) -> Self {
+        TcpContractReadFail::InvalidPayload(src)
+    }
+}
+
+impl TcpContractReadFail {
+    //TcpSocketSerializer of my-tcp-sockets can only return ReadingTcpContractFail, which has no case for a malformed payload.
+    //Connection is dropped in both cases anyway. Use TcpContract::deserialize directly to tell them apart
+    pub fn into_reading_fail(self) -> ReadingTcpContractFail {
+        match self {
+            TcpContractReadFail::Socket(err) => err,
+            TcpContractReadFail::InvalidPayload(_) => ReadingTcpContractFail::ErrorReadingSize,
+        }
+    }
+}
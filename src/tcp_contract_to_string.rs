@@ -5,8 +5,11 @@ const PONG_NAME: &str = "Pong";
 const GREETING_NAME: &str = "Greeting";
 const PUBLISH_NAME: &str = "Publish";
 const PUBLISH_RESPONSE_NAME: &str = "PublishResponse";
+const PUBLISH_DETAILED_RESPONSE_NAME: &str = "PublishDetailedResponse";
 const SUBSCRIBE_NAME: &str = "Subscribe";
 const SUBSCRIBER_RESPONSE: &str = "SubscribeResponse";
+const PAUSE_SUBSCRIPTION: &str = "PauseSubscription";
+const RESUME_SUBSCRIPTION: &str = "ResumeSubscription";
 const NEW_MESSAGES: &str = "NewMessages";
 const RAW_PAYLOAD: &str = "RawPayload";
 
@@ -23,6 +26,44 @@ const ALL_MESSAGES_CONFIRMED_AS_FAIL: &str = "AllMessagesConfirmedAsFail";
 
 const CONFIRM_SOME_MESSAGES_AS_OK: &str = "ConfirmSomeMessagesAsOk";
 
+const CONFIRM_SOME_MESSAGES_AS_FAIL: &str = "ConfirmSomeMessagesAsFail";
+
+const EXTEND_DELIVERY_LEASE: &str = "ExtendDeliveryLease";
+
+const GRANT_CREDITS: &str = "GrantCredits";
+
+const THROTTLE_PUBLISH: &str = "ThrottlePublish";
+const RESUME_PUBLISH: &str = "ResumePublish";
+
+const UNSUBSCRIBE: &str = "Unsubscribe";
+const UNSUBSCRIBE_RESPONSE: &str = "UnsubscribeResponse";
+
+const SUBSCRIBE_BY_PATTERN: &str = "SubscribeByPattern";
+const PATTERN_TOPIC_SUBSCRIBED: &str = "PatternTopicSubscribed";
+
+const FETCH_MESSAGES: &str = "FetchMessages";
+const FETCH_MESSAGES_RESPONSE: &str = "FetchMessagesResponse";
+
+const PEEK_MESSAGES: &str = "PeekMessages";
+const PEEK_MESSAGES_RESPONSE: &str = "PeekMessagesResponse";
+
+const DECLARE_QUEUE: &str = "DeclareQueue";
+const DELETE_QUEUE: &str = "DeleteQueue";
+const DELETE_TOPIC: &str = "DeleteTopic";
+const PURGE_QUEUE: &str = "PurgeQueue";
+const MANAGEMENT_RESPONSE: &str = "ManagementResponse";
+const CREATE_TOPIC_IF_NOT_EXISTS_RESPONSE: &str = "CreateTopicIfNotExistsResponse";
+
+const GET_QUEUE_STATS: &str = "GetQueueStats";
+const GET_QUEUE_STATS_RESPONSE: &str = "GetQueueStatsResponse";
+
+const LIST_TOPICS: &str = "ListTopics";
+const LIST_TOPICS_RESPONSE: &str = "ListTopicsResponse";
+const LIST_QUEUES: &str = "ListQueues";
+const LIST_QUEUES_RESPONSE: &str = "ListQueuesResponse";
+const LIST_CONNECTIONS: &str = "ListConnections";
+const LIST_CONNECTIONS_RESPONSE: &str = "ListConnectionsResponse";
+
 impl TcpContract {
     pub fn to_string(&self) -> &'static str {
         match self {
@@ -39,20 +80,37 @@ impl TcpContract {
                 data_to_publish: _,
             } => PUBLISH_NAME,
             TcpContract::PublishResponse { request_id: _ } => PUBLISH_RESPONSE_NAME,
+            TcpContract::PublishDetailedResponse {
+                request_id: _,
+                statuses: _,
+            } => PUBLISH_DETAILED_RESPONSE_NAME,
             TcpContract::Subscribe {
                 topic_id: _,
                 queue_id: _,
                 queue_type: _,
+                prefetch: _,
+                start_position: _,
+                filter: _,
             } => SUBSCRIBE_NAME,
             TcpContract::SubscribeResponse {
                 topic_id: _,
                 queue_id: _,
+                details: _,
             } => SUBSCRIBER_RESPONSE,
+            TcpContract::PauseSubscription {
+                topic_id: _,
+                queue_id: _,
+            } => PAUSE_SUBSCRIPTION,
+            TcpContract::ResumeSubscription {
+                topic_id: _,
+                queue_id: _,
+            } => RESUME_SUBSCRIPTION,
             TcpContract::NewMessages {
                 topic_id: _,
                 messages: _,
                 confirmation_id: _,
                 queue_id: _,
+                lag: _,
             } => NEW_MESSAGES,
             TcpContract::Raw(_) => RAW_PAYLOAD,
             TcpContract::NewMessagesConfirmation {
@@ -60,7 +118,10 @@ impl TcpContract {
                 queue_id: _,
                 confirmation_id: _,
             } => NEW_MESSAGES_CONFIRMATION,
-            TcpContract::CreateTopicIfNotExists { topic_id: _ } => CREATE_TOPIC_IF_EXIST,
+            TcpContract::CreateTopicIfNotExists {
+                topic_id: _,
+                settings: _,
+            } => CREATE_TOPIC_IF_EXIST,
             TcpContract::IntermediaryConfirm {
                 packet_version: _,
                 topic_id: _,
@@ -82,6 +143,154 @@ impl TcpContract {
                 confirmation_id: _,
                 delivered: _,
             } => CONFIRM_SOME_MESSAGES_AS_OK,
+            TcpContract::ConfirmSomeMessagesAsFail {
+                packet_version: _,
+                topic_id: _,
+                queue_id: _,
+                confirmation_id: _,
+                failed: _,
+            } => CONFIRM_SOME_MESSAGES_AS_FAIL,
+            TcpContract::ExtendDeliveryLease {
+                packet_version: _,
+                topic_id: _,
+                queue_id: _,
+                confirmation_id: _,
+                ranges: _,
+            } => EXTEND_DELIVERY_LEASE,
+            TcpContract::GrantCredits {
+                topic_id: _,
+                queue_id: _,
+                messages: _,
+                bytes: _,
+            } => GRANT_CREDITS,
+            TcpContract::ThrottlePublish {
+                topic_id: _,
+                max_messages_per_second: _,
+                pause: _,
+            } => THROTTLE_PUBLISH,
+            TcpContract::ResumePublish { topic_id: _ } => RESUME_PUBLISH,
+            TcpContract::Unsubscribe {
+                topic_id: _,
+                queue_id: _,
+            } => UNSUBSCRIBE,
+            TcpContract::UnsubscribeResponse {
+                topic_id: _,
+                queue_id: _,
+                cancelled_confirmation_ids: _,
+            } => UNSUBSCRIBE_RESPONSE,
+            TcpContract::SubscribeByPattern {
+                topic_pattern: _,
+                queue_id: _,
+                queue_type: _,
+            } => SUBSCRIBE_BY_PATTERN,
+            TcpContract::PatternTopicSubscribed {
+                topic_pattern: _,
+                topic_id: _,
+                queue_id: _,
+            } => PATTERN_TOPIC_SUBSCRIBED,
+            TcpContract::FetchMessages {
+                request_id: _,
+                topic_id: _,
+                queue_id: _,
+                max_messages: _,
+                max_bytes: _,
+                max_wait: _,
+            } => FETCH_MESSAGES,
+            TcpContract::FetchMessagesResponse {
+                request_id: _,
+                topic_id: _,
+                queue_id: _,
+                confirmation_id: _,
+                messages: _,
+            } => FETCH_MESSAGES_RESPONSE,
+            TcpContract::PeekMessages {
+                request_id: _,
+                topic_id: _,
+                queue_id: _,
+                from_id: _,
+                max_count: _,
+            } => PEEK_MESSAGES,
+            TcpContract::PeekMessagesResponse {
+                request_id: _,
+                topic_id: _,
+                queue_id: _,
+                messages: _,
+            } => PEEK_MESSAGES_RESPONSE,
+            TcpContract::DeclareQueue {
+                request_id: _,
+                topic_id: _,
+                queue_id: _,
+                queue_type: _,
+            } => DECLARE_QUEUE,
+            TcpContract::DeleteQueue {
+                request_id: _,
+                topic_id: _,
+                queue_id: _,
+            } => DELETE_QUEUE,
+            TcpContract::DeleteTopic {
+                request_id: _,
+                topic_id: _,
+            } => DELETE_TOPIC,
+            TcpContract::PurgeQueue {
+                request_id: _,
+                topic_id: _,
+                queue_id: _,
+            } => PURGE_QUEUE,
+            TcpContract::ManagementResponse {
+                request_id: _,
+                status: _,
+                error: _,
+            } => MANAGEMENT_RESPONSE,
+            TcpContract::CreateTopicIfNotExistsResponse {
+                topic_id: _,
+                status: _,
+                error: _,
+                settings: _,
+            } => CREATE_TOPIC_IF_NOT_EXISTS_RESPONSE,
+            TcpContract::GetQueueStats {
+                request_id: _,
+                topic_id: _,
+                queue_ids: _,
+            } => GET_QUEUE_STATS,
+            TcpContract::GetQueueStatsResponse {
+                request_id: _,
+                topic_id: _,
+                status: _,
+                queues: _,
+            } => GET_QUEUE_STATS_RESPONSE,
+            TcpContract::ListTopics {
+                request_id: _,
+                page_token: _,
+                max_count: _,
+            } => LIST_TOPICS,
+            TcpContract::ListTopicsResponse {
+                request_id: _,
+                topic_ids: _,
+                next_page_token: _,
+            } => LIST_TOPICS_RESPONSE,
+            TcpContract::ListQueues {
+                request_id: _,
+                topic_id: _,
+                page_token: _,
+                max_count: _,
+            } => LIST_QUEUES,
+            TcpContract::ListQueuesResponse {
+                request_id: _,
+                topic_id: _,
+                status: _,
+                queues: _,
+                next_page_token: _,
+            } => LIST_QUEUES_RESPONSE,
+            TcpContract::ListConnections {
+                request_id: _,
+                page_token: _,
+                max_count: _,
+            } => LIST_CONNECTIONS,
+            TcpContract::ListConnectionsResponse {
+                request_id: _,
+                connections: _,
+                next_page_token: _,
+            } => LIST_CONNECTIONS_RESPONSE,
         }
     }
 }
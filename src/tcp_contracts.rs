@@ -1,14 +1,18 @@
 use my_service_bus_abstractions::{
     publisher::MessageToPublish, queue_with_intervals::QueueIndexRange, subscriber::TopicQueueType,
-    MySbMessage,
 };
 use my_tcp_sockets::socket_reader::{ReadingTcpContractFail, SocketReader};
 
-use crate::ConnectionAttributes;
+use crate::{
+    ConnectionAttributes, ConnectionInfo, DeliveredMessage, FailedMessages, HeaderFilter,
+    ManagementStatus, MessageExpiration, PacketProtVer, PayloadError, PrefetchLimit,
+    PublishMessageOptions, PublishMessageStatus, PublishMessages, QueueInfo, QueueLag, QueueStats,
+    SubscribeResponseDetails, SubscribeStartPosition, TcpContractReadFail, TopicSettings,
+};
 
 use super::tcp_message_id::*;
 
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
 
 pub type RequestId = i64;
 
@@ -26,26 +30,52 @@ pub enum TcpContract {
         topic_id: String,
         request_id: RequestId,
         persist_immediately: bool,
-        data_to_publish: Vec<MessageToPublish>,
+        //Carries batch expiration and options of the messages as well
+        data_to_publish: PublishMessages,
     },
     PublishResponse {
         request_id: RequestId,
     },
+    //Same order as messages of the Publish packet
+    PublishDetailedResponse {
+        request_id: RequestId,
+        statuses: Vec<PublishMessageStatus>,
+    },
     Subscribe {
         topic_id: String,
         queue_id: String,
         queue_type: TopicQueueType,
+        //Packet version 1+
+        prefetch: Option<PrefetchLimit>,
+        //Packet version 2+
+        start_position: SubscribeStartPosition,
+        //Packet version 3+. Server delivers only messages which match the filter
+        filter: Option<HeaderFilter>,
     },
     SubscribeResponse {
         topic_id: String,
         queue_id: String,
+        //Packet version 1+. Legacy clients get only topic_id and queue_id
+        details: Option<SubscribeResponseDetails>,
+    },
+    //Server stops deliveries to the subscriber. Queue, connection and other subscriptions stay alive
+    PauseSubscription {
+        topic_id: String,
+        queue_id: String,
+    },
+    ResumeSubscription {
+        topic_id: String,
+        queue_id: String,
     },
     Raw(Vec<u8>),
     NewMessages {
         topic_id: String,
         queue_id: String,
         confirmation_id: i64,
-        messages: Vec<MySbMessage>,
+        //Details are delivered with packet version 3+. Default ones for older packet versions
+        messages: Vec<DeliveredMessage>,
+        //Packet version 2+. None - server did not provide lag for this delivery
+        lag: Option<QueueLag>,
     },
     NewMessagesConfirmation {
         topic_id: String,
@@ -54,6 +84,8 @@ pub enum TcpContract {
     },
     CreateTopicIfNotExists {
         topic_id: String,
+        //Packet version 1+. None - server defaults are used
+        settings: Option<TopicSettings>,
     },
     IntermediaryConfirm {
         packet_version: u8,
@@ -81,13 +113,218 @@ pub enum TcpContract {
         confirmation_id: ConfirmationId,
         delivered: Vec<QueueIndexRange>,
     },
+
+    ConfirmSomeMessagesAsFail {
+        packet_version: u8,
+        topic_id: String,
+        queue_id: String,
+        confirmation_id: ConfirmationId,
+        failed: Vec<FailedMessages>,
+    },
+
+    //Asks server to extend delivery timeout. Does not confirm any message
+    ExtendDeliveryLease {
+        packet_version: u8,
+        topic_id: String,
+        queue_id: String,
+        confirmation_id: ConfirmationId,
+        //None - lease is extended for every message of the delivery
+        ranges: Option<Vec<QueueIndexRange>>,
+    },
+
+    //Adds credits to the prefetch window of the subscriber as consumer finishes the work
+    GrantCredits {
+        topic_id: String,
+        queue_id: String,
+        messages: i32,
+        bytes: i64,
+    },
+
+    //Server to client. Must be sent only if client supports THROTTLE_PUBLISH packet
+    ThrottlePublish {
+        //None - throttle applies to every topic of the connection
+        topic_id: Option<String>,
+        max_messages_per_second: Option<i32>,
+        pause: Option<Duration>,
+    },
+
+    //Server to client. Must be sent only if client supports THROTTLE_PUBLISH packet
+    ResumePublish {
+        //None - resume every topic of the connection
+        topic_id: Option<String>,
+    },
+
+    //Drops single subscription keeping connection and other subscriptions alive.
+    //Deliveries which are not confirmed yet are returned to the queue as not delivered
+    //and confirmations which come for them after Unsubscribe are ignored
+    Unsubscribe {
+        topic_id: String,
+        queue_id: String,
+    },
+
+    UnsubscribeResponse {
+        topic_id: String,
+        queue_id: String,
+        //Deliveries returned to the queue. Client should stop processing them
+        cancelled_confirmation_ids: Vec<ConfirmationId>,
+    },
+
+    //Subscribes queue to every existing and future topic which matches the pattern.
+    //Pattern semantics are defined by topic_pattern::is_match
+    SubscribeByPattern {
+        topic_pattern: String,
+        queue_id: String,
+        queue_type: TopicQueueType,
+    },
+
+    //Server to client. Sent for every topic matching the pattern: existing ones and new ones as they appear.
+    //Messages are delivered with NewMessages packet of the topic
+    PatternTopicSubscribed {
+        topic_pattern: String,
+        topic_id: String,
+        queue_id: String,
+    },
+
+    //Pull mode. Server replies with FetchMessagesResponse as soon as it has messages or max_wait is elapsed
+    FetchMessages {
+        request_id: RequestId,
+        topic_id: String,
+        queue_id: String,
+        max_messages: i32,
+        max_bytes: i64,
+        max_wait: Duration,
+    },
+
+    //Messages can be empty. Not empty batch is confirmed the same way as NewMessages
+    FetchMessagesResponse {
+        request_id: RequestId,
+        topic_id: String,
+        queue_id: String,
+        confirmation_id: ConfirmationId,
+        //Details are serialized according to NewMessages packet version
+        messages: Vec<DeliveredMessage>,
+    },
+
+    //Reads messages without registering delivery. Attempt numbers are not increased
+    PeekMessages {
+        request_id: RequestId,
+        topic_id: String,
+        queue_id: String,
+        from_id: i64,
+        max_count: i32,
+    },
+
+    //No confirmation_id - peeked messages must not be confirmed
+    PeekMessagesResponse {
+        request_id: RequestId,
+        topic_id: String,
+        queue_id: String,
+        //Details are serialized according to NewMessages packet version
+        messages: Vec<DeliveredMessage>,
+    },
+
+    //Creates queue without subscribing to it
+    DeclareQueue {
+        request_id: RequestId,
+        topic_id: String,
+        queue_id: String,
+        queue_type: TopicQueueType,
+    },
+
+    DeleteQueue {
+        request_id: RequestId,
+        topic_id: String,
+        queue_id: String,
+    },
+
+    DeleteTopic {
+        request_id: RequestId,
+        topic_id: String,
+    },
+
+    //Removes all the messages from the queue. Queue itself stays
+    PurgeQueue {
+        request_id: RequestId,
+        topic_id: String,
+        queue_id: String,
+    },
+
+    ManagementResponse {
+        request_id: RequestId,
+        status: ManagementStatus,
+        error: Option<String>,
+    },
+
+    //Settings which are actually applied to the topic. They can differ from requested ones
+    //if topic already exists or server limits are lower. No settings if topic was not created
+    CreateTopicIfNotExistsResponse {
+        topic_id: String,
+        status: ManagementStatus,
+        error: Option<String>,
+        settings: Option<TopicSettings>,
+    },
+
+    GetQueueStats {
+        request_id: RequestId,
+        topic_id: String,
+        //Empty - stats of all the queues of the topic are requested
+        queue_ids: Vec<String>,
+    },
+
+    GetQueueStatsResponse {
+        request_id: RequestId,
+        topic_id: String,
+        status: ManagementStatus,
+        queues: Vec<QueueStats>,
+    },
+
+    //Listings are paginated. page_token: None - first page is requested.
+    //next_page_token: None - there are no more pages
+    ListTopics {
+        request_id: RequestId,
+        page_token: Option<String>,
+        max_count: i32,
+    },
+
+    ListTopicsResponse {
+        request_id: RequestId,
+        topic_ids: Vec<String>,
+        next_page_token: Option<String>,
+    },
+
+    ListQueues {
+        request_id: RequestId,
+        topic_id: String,
+        page_token: Option<String>,
+        max_count: i32,
+    },
+
+    ListQueuesResponse {
+        request_id: RequestId,
+        topic_id: String,
+        status: ManagementStatus,
+        queues: Vec<QueueInfo>,
+        next_page_token: Option<String>,
+    },
+
+    ListConnections {
+        request_id: RequestId,
+        page_token: Option<String>,
+        max_count: i32,
+    },
+
+    ListConnectionsResponse {
+        request_id: RequestId,
+        connections: Vec<ConnectionInfo>,
+        next_page_token: Option<String>,
+    },
 }
 
 impl TcpContract {
     pub async fn deserialize<TSocketReader: SocketReader>(
         socket_reader: &mut TSocketReader,
         attr: &ConnectionAttributes,
-    ) -> Result<TcpContract, ReadingTcpContractFail> {
+    ) -> Result<TcpContract, TcpContractReadFail> {
         let packet_no = socket_reader.read_byte().await?;
 
         let result = match packet_no {
@@ -109,9 +346,14 @@ impl TcpContract {
                     crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
                 let request_id = socket_reader.read_i64().await?;
 
-                let messages_count = socket_reader.read_i32().await? as usize;
+                let messages_count =
+                    crate::tcp_serializers::messages_to_deliver::deserialize_list_len(
+                        socket_reader,
+                    )
+                    .await?;
 
-                let mut data_to_publish: Vec<MessageToPublish> = Vec::with_capacity(messages_count);
+                let mut data_to_publish: Vec<MessageToPublish> =
+                    crate::tcp_serializers::messages_to_deliver::create_list(messages_count);
 
                 if attr.protocol_version < 3 {
                     for _ in 0..messages_count {
@@ -131,11 +373,35 @@ impl TcpContract {
                     }
                 }
 
+                let persist_immediately = socket_reader.read_bool().await?;
+
+                let packet_version = attr.get_packet_version(packet_no);
+
+                let (expiration, options) = if packet_version >= 1 {
+                    let expiration =
+                        crate::tcp_serializers::message_expiration::deserialize(socket_reader)
+                            .await?;
+                    let options = crate::tcp_serializers::messages_to_publish::deserialize_options(
+                        socket_reader,
+                        data_to_publish.len(),
+                        expiration.as_ref(),
+                        packet_version,
+                    )
+                    .await?;
+                    (expiration, options)
+                } else {
+                    (None, Vec::new())
+                };
+
                 let result = TcpContract::Publish {
                     topic_id,
                     request_id,
-                    data_to_publish,
-                    persist_immediately: socket_reader.read_bool().await?,
+                    data_to_publish: PublishMessages::with_options(
+                        data_to_publish,
+                        expiration,
+                        options,
+                    )?,
+                    persist_immediately,
                 };
                 Ok(result)
             }
@@ -145,6 +411,26 @@ impl TcpContract {
 
                 Ok(result)
             }
+            PUBLISH_DETAILED_RESPONSE => {
+                let request_id = socket_reader.read_i64().await?;
+
+                let len = socket_reader.read_i32().await?;
+
+                let mut statuses = Vec::new();
+
+                for _ in 0..len {
+                    statuses.push(PublishMessageStatus::from_u8(
+                        socket_reader.read_byte().await?,
+                    )?);
+                }
+
+                let result = TcpContract::PublishDetailedResponse {
+                    request_id,
+                    statuses,
+                };
+
+                Ok(result)
+            }
             SUBSCRIBE => {
                 let topic_id =
                     crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
@@ -154,10 +440,34 @@ impl TcpContract {
 
                 let queue_type = TopicQueueType::from_u8(queue_type);
 
+                let packet_version = attr.get_packet_version(packet_no);
+
+                let prefetch = if packet_version >= 1 {
+                    crate::tcp_serializers::prefetch_limit::deserialize(socket_reader).await?
+                } else {
+                    None
+                };
+
+                let start_position = if packet_version >= 2 {
+                    crate::tcp_serializers::subscribe_start_position::deserialize(socket_reader)
+                        .await?
+                } else {
+                    SubscribeStartPosition::ServerDefault
+                };
+
+                let filter = if packet_version >= 3 {
+                    crate::tcp_serializers::header_filter::deserialize(socket_reader).await?
+                } else {
+                    None
+                };
+
                 let result = TcpContract::Subscribe {
                     topic_id,
                     queue_id,
                     queue_type,
+                    prefetch,
+                    start_position,
+                    filter,
                 };
 
                 Ok(result)
@@ -167,7 +477,38 @@ impl TcpContract {
                     crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
                 let queue_id =
                     crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
-                let result = TcpContract::SubscribeResponse { topic_id, queue_id };
+
+                let details = if attr.get_packet_version(packet_no) >= 1 {
+                    crate::tcp_serializers::subscribe_response_details::deserialize(socket_reader)
+                        .await?
+                } else {
+                    None
+                };
+
+                let result = TcpContract::SubscribeResponse {
+                    topic_id,
+                    queue_id,
+                    details,
+                };
+
+                Ok(result)
+            }
+
+            PAUSE_SUBSCRIPTION => {
+                let topic_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let queue_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let result = TcpContract::PauseSubscription { topic_id, queue_id };
+
+                Ok(result)
+            }
+            RESUME_SUBSCRIPTION => {
+                let topic_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let queue_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let result = TcpContract::ResumeSubscription { topic_id, queue_id };
 
                 Ok(result)
             }
@@ -179,24 +520,25 @@ impl TcpContract {
                     crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
                 let confirmation_id = socket_reader.read_i64().await?;
 
-                let records_len = socket_reader.read_i32().await? as usize;
-
-                let mut messages = Vec::with_capacity(records_len);
                 let version = attr.get(packet_no);
-                for _ in 0..records_len {
-                    let msg = crate::tcp_serializers::messages_to_deliver::deserialize(
-                        socket_reader,
-                        &version,
-                    )
-                    .await?;
-                    messages.push(msg);
-                }
+                let messages = crate::tcp_serializers::messages_to_deliver::deserialize_list(
+                    socket_reader,
+                    &version,
+                )
+                .await?;
+
+                let lag = if version.packet_version >= 2 {
+                    crate::tcp_serializers::queue_lag::deserialize(socket_reader).await?
+                } else {
+                    None
+                };
 
                 let result = TcpContract::NewMessages {
                     topic_id,
                     queue_id,
                     confirmation_id,
                     messages,
+                    lag,
                 };
 
                 Ok(result)
@@ -220,7 +562,19 @@ impl TcpContract {
                 let topic_id =
                     crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
 
-                let result = TcpContract::CreateTopicIfNotExists { topic_id };
+                let packet_version = attr.get_packet_version(packet_no);
+
+                let settings = if packet_version >= 1 {
+                    crate::tcp_serializers::topic_settings::deserialize(
+                        socket_reader,
+                        packet_version,
+                    )
+                    .await?
+                } else {
+                    None
+                };
+
+                let result = TcpContract::CreateTopicIfNotExists { topic_id, settings };
 
                 Ok(result)
             }
@@ -273,8 +627,11 @@ impl TcpContract {
                 let confirmation_id = socket_reader.read_i64().await?;
 
                 let delivered =
-                    crate::tcp_serializers::queue_with_intervals::deserialize(socket_reader)
-                        .await?;
+                    crate::tcp_serializers::queue_with_intervals::deserialize_with_mode(
+                        socket_reader,
+                        attr.strict_queue_ranges,
+                    )
+                    .await?;
 
                 let result = TcpContract::ConfirmSomeMessagesAsOk {
                     packet_version,
@@ -296,8 +653,11 @@ impl TcpContract {
                 let confirmation_id = socket_reader.read_i64().await?;
 
                 let delivered =
-                    crate::tcp_serializers::queue_with_intervals::deserialize(socket_reader)
-                        .await?;
+                    crate::tcp_serializers::queue_with_intervals::deserialize_with_mode(
+                        socket_reader,
+                        attr.strict_queue_ranges,
+                    )
+                    .await?;
 
                 let result = TcpContract::IntermediaryConfirm {
                     packet_version,
@@ -310,273 +670,3421 @@ impl TcpContract {
                 Ok(result)
             }
 
-            _ => Err(ReadingTcpContractFail::InvalidPacketId(packet_no)),
-        };
+            CONFIRM_SOME_MESSAGES_AS_FAIL => {
+                let packet_version = socket_reader.read_byte().await?;
+                let topic_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let queue_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let confirmation_id = socket_reader.read_i64().await?;
 
-        return result;
-    }
+                let failed = crate::tcp_serializers::failed_messages::deserialize(
+                    socket_reader,
+                    attr.strict_queue_ranges,
+                )
+                .await?;
 
-    pub fn serialize(self, protocol_version: i32) -> Vec<u8> {
-        match self {
-            TcpContract::Ping {} => {
-                let mut result: Vec<u8> = Vec::with_capacity(1);
-                result.push(PING);
-                result
-            }
-            TcpContract::Pong {} => {
-                let mut result: Vec<u8> = Vec::with_capacity(1);
-                result.push(PONG);
-                result
-            }
-            TcpContract::Greeting {
-                name,
-                protocol_version,
-            } => {
-                let mut result: Vec<u8> = Vec::new();
-                result.push(GREETING);
-                crate::tcp_serializers::pascal_string::serialize(&mut result, name.as_str());
-                crate::tcp_serializers::i32::serialize(&mut result, protocol_version);
-                result
-            }
-            TcpContract::Publish {
-                topic_id,
-                request_id,
-                persist_immediately,
-                data_to_publish,
-            } => Self::compile_publish_payload(
-                topic_id.as_str(),
-                request_id,
-                data_to_publish.as_slice(),
-                persist_immediately,
-                protocol_version,
-            ),
+                let result = TcpContract::ConfirmSomeMessagesAsFail {
+                    packet_version,
+                    topic_id,
+                    queue_id,
+                    confirmation_id,
+                    failed,
+                };
 
-            TcpContract::PublishResponse { request_id } => {
-                let mut result: Vec<u8> = Vec::new();
-                result.push(PUBLISH_RESPONSE);
-                crate::tcp_serializers::i64::serialize(&mut result, request_id);
-                result
+                Ok(result)
             }
-            TcpContract::Subscribe {
-                topic_id,
-                queue_id,
-                queue_type,
-            } => {
-                let mut result: Vec<u8> = Vec::new();
-                result.push(SUBSCRIBE);
-                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+
+            EXTEND_DELIVERY_LEASE => {
+                let packet_version = socket_reader.read_byte().await?;
+                let topic_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let queue_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let confirmation_id = socket_reader.read_i64().await?;
+
+                let ranges = if socket_reader.read_bool().await? {
+                    let ranges =
+                        crate::tcp_serializers::queue_with_intervals::deserialize_with_mode(
+                            socket_reader,
+                            attr.strict_queue_ranges,
+                        )
+                        .await?;
+                    Some(ranges)
+                } else {
+                    None
+                };
+
+                let result = TcpContract::ExtendDeliveryLease {
+                    packet_version,
+                    topic_id,
+                    queue_id,
+                    confirmation_id,
+                    ranges,
+                };
+
+                Ok(result)
+            }
+
+            GRANT_CREDITS => {
+                let topic_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let queue_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let messages = socket_reader.read_i32().await?;
+                let bytes = socket_reader.read_i64().await?;
+
+                let result = TcpContract::GrantCredits {
+                    topic_id,
+                    queue_id,
+                    messages,
+                    bytes,
+                };
+
+                Ok(result)
+            }
+
+            THROTTLE_PUBLISH => {
+                let topic_id =
+                    crate::tcp_serializers::optional_pascal_string::deserialize(socket_reader)
+                        .await?;
+
+                let max_messages_per_second = if socket_reader.read_bool().await? {
+                    Some(socket_reader.read_i32().await?)
+                } else {
+                    None
+                };
+
+                let pause = if socket_reader.read_bool().await? {
+                    Some(crate::tcp_serializers::duration::deserialize(socket_reader).await?)
+                } else {
+                    None
+                };
+
+                let result = TcpContract::ThrottlePublish {
+                    topic_id,
+                    max_messages_per_second,
+                    pause,
+                };
+
+                Ok(result)
+            }
+
+            RESUME_PUBLISH => {
+                let topic_id =
+                    crate::tcp_serializers::optional_pascal_string::deserialize(socket_reader)
+                        .await?;
+                let result = TcpContract::ResumePublish { topic_id };
+                Ok(result)
+            }
+
+            UNSUBSCRIBE => {
+                let topic_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let queue_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+
+                let result = TcpContract::Unsubscribe { topic_id, queue_id };
+
+                Ok(result)
+            }
+
+            UNSUBSCRIBE_RESPONSE => {
+                let topic_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let queue_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+
+                let len = socket_reader.read_i32().await?;
+
+                let mut cancelled_confirmation_ids = Vec::new();
+
+                for _ in 0..len {
+                    cancelled_confirmation_ids.push(socket_reader.read_i64().await?);
+                }
+
+                let result = TcpContract::UnsubscribeResponse {
+                    topic_id,
+                    queue_id,
+                    cancelled_confirmation_ids,
+                };
+
+                Ok(result)
+            }
+
+            SUBSCRIBE_BY_PATTERN => {
+                let topic_pattern =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let queue_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let queue_type = socket_reader.read_byte().await?;
+
+                let queue_type = TopicQueueType::from_u8(queue_type);
+
+                let result = TcpContract::SubscribeByPattern {
+                    topic_pattern,
+                    queue_id,
+                    queue_type,
+                };
+
+                Ok(result)
+            }
+
+            PATTERN_TOPIC_SUBSCRIBED => {
+                let topic_pattern =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let topic_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let queue_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+
+                let result = TcpContract::PatternTopicSubscribed {
+                    topic_pattern,
+                    topic_id,
+                    queue_id,
+                };
+
+                Ok(result)
+            }
+
+            FETCH_MESSAGES => {
+                let request_id = socket_reader.read_i64().await?;
+                let topic_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let queue_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let max_messages = socket_reader.read_i32().await?;
+                let max_bytes = socket_reader.read_i64().await?;
+                let max_wait = crate::tcp_serializers::duration::deserialize(socket_reader).await?;
+
+                let result = TcpContract::FetchMessages {
+                    request_id,
+                    topic_id,
+                    queue_id,
+                    max_messages,
+                    max_bytes,
+                    max_wait,
+                };
+
+                Ok(result)
+            }
+
+            FETCH_MESSAGES_RESPONSE => {
+                let request_id = socket_reader.read_i64().await?;
+                let topic_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let queue_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let confirmation_id = socket_reader.read_i64().await?;
+
+                let messages = crate::tcp_serializers::messages_to_deliver::deserialize_list(
+                    socket_reader,
+                    &attr.get(NEW_MESSAGES),
+                )
+                .await?;
+
+                let result = TcpContract::FetchMessagesResponse {
+                    request_id,
+                    topic_id,
+                    queue_id,
+                    confirmation_id,
+                    messages,
+                };
+
+                Ok(result)
+            }
+
+            PEEK_MESSAGES => {
+                let request_id = socket_reader.read_i64().await?;
+                let topic_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let queue_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let from_id = socket_reader.read_i64().await?;
+                let max_count = socket_reader.read_i32().await?;
+
+                let result = TcpContract::PeekMessages {
+                    request_id,
+                    topic_id,
+                    queue_id,
+                    from_id,
+                    max_count,
+                };
+
+                Ok(result)
+            }
+
+            PEEK_MESSAGES_RESPONSE => {
+                let request_id = socket_reader.read_i64().await?;
+                let topic_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let queue_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+
+                let messages = crate::tcp_serializers::messages_to_deliver::deserialize_list(
+                    socket_reader,
+                    &attr.get(NEW_MESSAGES),
+                )
+                .await?;
+
+                let result = TcpContract::PeekMessagesResponse {
+                    request_id,
+                    topic_id,
+                    queue_id,
+                    messages,
+                };
+
+                Ok(result)
+            }
+
+            DECLARE_QUEUE => {
+                let request_id = socket_reader.read_i64().await?;
+                let topic_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let queue_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let queue_type = TopicQueueType::from_u8(socket_reader.read_byte().await?);
+
+                let result = TcpContract::DeclareQueue {
+                    request_id,
+                    topic_id,
+                    queue_id,
+                    queue_type,
+                };
+
+                Ok(result)
+            }
+
+            DELETE_QUEUE => {
+                let request_id = socket_reader.read_i64().await?;
+                let topic_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let queue_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+
+                let result = TcpContract::DeleteQueue {
+                    request_id,
+                    topic_id,
+                    queue_id,
+                };
+
+                Ok(result)
+            }
+
+            DELETE_TOPIC => {
+                let request_id = socket_reader.read_i64().await?;
+                let topic_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+
+                let result = TcpContract::DeleteTopic {
+                    request_id,
+                    topic_id,
+                };
+
+                Ok(result)
+            }
+
+            PURGE_QUEUE => {
+                let request_id = socket_reader.read_i64().await?;
+                let topic_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let queue_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+
+                let result = TcpContract::PurgeQueue {
+                    request_id,
+                    topic_id,
+                    queue_id,
+                };
+
+                Ok(result)
+            }
+
+            MANAGEMENT_RESPONSE => {
+                let request_id = socket_reader.read_i64().await?;
+                let status = ManagementStatus::from_u8(socket_reader.read_byte().await?)?;
+                let error =
+                    crate::tcp_serializers::optional_pascal_string::deserialize(socket_reader)
+                        .await?;
+
+                let result = TcpContract::ManagementResponse {
+                    request_id,
+                    status,
+                    error,
+                };
+
+                Ok(result)
+            }
+
+            CREATE_TOPIC_IF_NOT_EXISTS_RESPONSE => {
+                let topic_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let status = ManagementStatus::from_u8(socket_reader.read_byte().await?)?;
+                let error =
+                    crate::tcp_serializers::optional_pascal_string::deserialize(socket_reader)
+                        .await?;
+                //Settings layout is the same as the one of the request
+                let settings = crate::tcp_serializers::topic_settings::deserialize(
+                    socket_reader,
+                    attr.get_packet_version(CREATE_TOPIC_IF_NOT_EXISTS),
+                )
+                .await?;
+
+                let result = TcpContract::CreateTopicIfNotExistsResponse {
+                    topic_id,
+                    status,
+                    error,
+                    settings,
+                };
+
+                Ok(result)
+            }
+
+            GET_QUEUE_STATS => {
+                let request_id = socket_reader.read_i64().await?;
+                let topic_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let queue_ids =
+                    crate::tcp_serializers::list_of_pascal_strings::deserialize(socket_reader)
+                        .await?;
+
+                let result = TcpContract::GetQueueStats {
+                    request_id,
+                    topic_id,
+                    queue_ids,
+                };
+
+                Ok(result)
+            }
+
+            GET_QUEUE_STATS_RESPONSE => {
+                let request_id = socket_reader.read_i64().await?;
+                let topic_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let status = ManagementStatus::from_u8(socket_reader.read_byte().await?)?;
+                let queues =
+                    crate::tcp_serializers::queue_stats::deserialize(socket_reader).await?;
+
+                let result = TcpContract::GetQueueStatsResponse {
+                    request_id,
+                    topic_id,
+                    status,
+                    queues,
+                };
+
+                Ok(result)
+            }
+
+            LIST_TOPICS => {
+                let request_id = socket_reader.read_i64().await?;
+                let page_token =
+                    crate::tcp_serializers::optional_pascal_string::deserialize(socket_reader)
+                        .await?;
+                let max_count = socket_reader.read_i32().await?;
+
+                let result = TcpContract::ListTopics {
+                    request_id,
+                    page_token,
+                    max_count,
+                };
+
+                Ok(result)
+            }
+
+            LIST_TOPICS_RESPONSE => {
+                let request_id = socket_reader.read_i64().await?;
+                let topic_ids =
+                    crate::tcp_serializers::list_of_pascal_strings::deserialize(socket_reader)
+                        .await?;
+                let next_page_token =
+                    crate::tcp_serializers::optional_pascal_string::deserialize(socket_reader)
+                        .await?;
+
+                let result = TcpContract::ListTopicsResponse {
+                    request_id,
+                    topic_ids,
+                    next_page_token,
+                };
+
+                Ok(result)
+            }
+
+            LIST_QUEUES => {
+                let request_id = socket_reader.read_i64().await?;
+                let topic_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let page_token =
+                    crate::tcp_serializers::optional_pascal_string::deserialize(socket_reader)
+                        .await?;
+                let max_count = socket_reader.read_i32().await?;
+
+                let result = TcpContract::ListQueues {
+                    request_id,
+                    topic_id,
+                    page_token,
+                    max_count,
+                };
+
+                Ok(result)
+            }
+
+            LIST_QUEUES_RESPONSE => {
+                let request_id = socket_reader.read_i64().await?;
+                let topic_id =
+                    crate::tcp_serializers::pascal_string::deserialize(socket_reader).await?;
+                let status = ManagementStatus::from_u8(socket_reader.read_byte().await?)?;
+                let queues = crate::tcp_serializers::queue_info::deserialize(socket_reader).await?;
+                let next_page_token =
+                    crate::tcp_serializers::optional_pascal_string::deserialize(socket_reader)
+                        .await?;
+
+                let result = TcpContract::ListQueuesResponse {
+                    request_id,
+                    topic_id,
+                    status,
+                    queues,
+                    next_page_token,
+                };
+
+                Ok(result)
+            }
+
+            LIST_CONNECTIONS => {
+                let request_id = socket_reader.read_i64().await?;
+                let page_token =
+                    crate::tcp_serializers::optional_pascal_string::deserialize(socket_reader)
+                        .await?;
+                let max_count = socket_reader.read_i32().await?;
+
+                let result = TcpContract::ListConnections {
+                    request_id,
+                    page_token,
+                    max_count,
+                };
+
+                Ok(result)
+            }
+
+            LIST_CONNECTIONS_RESPONSE => {
+                let request_id = socket_reader.read_i64().await?;
+                let connections = crate::tcp_serializers::connection_info::deserialize(
+                    socket_reader,
+                    attr.get_packet_version(packet_no),
+                )
+                .await?;
+                let next_page_token =
+                    crate::tcp_serializers::optional_pascal_string::deserialize(socket_reader)
+                        .await?;
+
+                let result = TcpContract::ListConnectionsResponse {
+                    request_id,
+                    connections,
+                    next_page_token,
+                };
+
+                Ok(result)
+            }
+
+            _ => Err(ReadingTcpContractFail::InvalidPacketId(packet_no).into()),
+        };
+
+        return result;
+    }
+
+    //Connection attributes built from protocol version only have no packet versions,
+    //so every packet is serialized as version 0 and all the versioned fields are lost
+    #[deprecated(note = "Use serialize_with_attr with the attributes of the connection")]
+    pub fn serialize(self, protocol_version: i32) -> Vec<u8> {
+        let attr = ConnectionAttributes::new(protocol_version);
+        self.serialize_with_attr(&attr)
+    }
+
+    //Packets which have several versions are serialized according to the versions of the connection
+    pub fn serialize_with_attr(self, attr: &ConnectionAttributes) -> Vec<u8> {
+        match self {
+            TcpContract::Ping {} => {
+                let mut result: Vec<u8> = Vec::with_capacity(1);
+                result.push(PING);
+                result
+            }
+            TcpContract::Pong {} => {
+                let mut result: Vec<u8> = Vec::with_capacity(1);
+                result.push(PONG);
+                result
+            }
+            TcpContract::Greeting {
+                name,
+                protocol_version,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(GREETING);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, name.as_str());
+                crate::tcp_serializers::i32::serialize(&mut result, protocol_version);
+                result
+            }
+            TcpContract::Publish {
+                topic_id,
+                request_id,
+                persist_immediately,
+                data_to_publish,
+            } => Self::compile_checked_publish_payload(
+                topic_id.as_str(),
+                request_id,
+                data_to_publish.get_messages(),
+                persist_immediately,
+                data_to_publish.get_expiration(),
+                data_to_publish.get_options(),
+                &attr.get(PUBLISH),
+            )
+            //Peer can not decode some of the options - packet is not sent.
+            //Use PublishMessages::check_packet_version before sending to get the reason
+            .unwrap_or_default(),
+
+            TcpContract::PublishResponse { request_id } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(PUBLISH_RESPONSE);
+                crate::tcp_serializers::i64::serialize(&mut result, request_id);
+                result
+            }
+            TcpContract::PublishDetailedResponse {
+                request_id,
+                statuses,
+            } => {
+                //Client which does not send dedup keys does not expect statuses either
+                if attr.get_packet_version(PUBLISH) < 3 {
+                    return TcpContract::PublishResponse { request_id }.serialize_with_attr(attr);
+                }
+
+                let mut result: Vec<u8> = Vec::new();
+                result.push(PUBLISH_DETAILED_RESPONSE);
+                crate::tcp_serializers::i64::serialize(&mut result, request_id);
+                crate::tcp_serializers::i32::serialize(&mut result, statuses.len() as i32);
+
+                for status in statuses {
+                    crate::tcp_serializers::byte::serialize(&mut result, status.into_u8());
+                }
+
+                result
+            }
+            TcpContract::Subscribe {
+                topic_id,
+                queue_id,
+                queue_type,
+                prefetch,
+                start_position,
+                filter,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(SUBSCRIBE);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+                crate::tcp_serializers::pascal_string::serialize(&mut result, queue_id.as_str());
+                crate::tcp_serializers::byte::serialize(&mut result, queue_type.into_u8());
+
+                let packet_version = attr.get_packet_version(SUBSCRIBE);
+
+                if packet_version >= 1 {
+                    crate::tcp_serializers::prefetch_limit::serialize(
+                        &mut result,
+                        prefetch.as_ref(),
+                    );
+                }
+
+                if packet_version >= 2 {
+                    crate::tcp_serializers::subscribe_start_position::serialize(
+                        &mut result,
+                        &start_position,
+                    );
+                }
+
+                if packet_version >= 3 {
+                    crate::tcp_serializers::header_filter::serialize(&mut result, filter.as_ref());
+                }
+
+                result
+            }
+            TcpContract::SubscribeResponse {
+                topic_id,
+                queue_id,
+                details,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(SUBSCRIBE_RESPONSE);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+                crate::tcp_serializers::pascal_string::serialize(&mut result, queue_id.as_str());
+
+                if attr.get_packet_version(SUBSCRIBE_RESPONSE) >= 1 {
+                    crate::tcp_serializers::subscribe_response_details::serialize(
+                        &mut result,
+                        details,
+                    );
+                }
+
+                result
+            }
+            TcpContract::PauseSubscription { topic_id, queue_id } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(PAUSE_SUBSCRIPTION);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+                crate::tcp_serializers::pascal_string::serialize(&mut result, queue_id.as_str());
+                result
+            }
+            TcpContract::ResumeSubscription { topic_id, queue_id } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(RESUME_SUBSCRIPTION);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+                crate::tcp_serializers::pascal_string::serialize(&mut result, queue_id.as_str());
+                result
+            }
+            TcpContract::Raw(payload) => payload,
+            TcpContract::NewMessages {
+                topic_id: _,
+                queue_id: _,
+                confirmation_id: _,
+                messages: _,
+                lag: _,
+            } => {
+                panic!(
+                    "This packet is not used by server. Server uses optimized version of the packet"
+                );
+            }
+            TcpContract::NewMessagesConfirmation {
+                topic_id,
+                queue_id,
+                confirmation_id,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(ALL_MESSAGES_DELIVERED_CONFIRMATION);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+                crate::tcp_serializers::pascal_string::serialize(&mut result, queue_id.as_str());
+                crate::tcp_serializers::i64::serialize(&mut result, confirmation_id);
+                result
+            }
+            TcpContract::CreateTopicIfNotExists { topic_id, settings } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(CREATE_TOPIC_IF_NOT_EXISTS);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+
+                let packet_version = attr.get_packet_version(CREATE_TOPIC_IF_NOT_EXISTS);
+
+                if packet_version >= 1 {
+                    crate::tcp_serializers::topic_settings::serialize(
+                        &mut result,
+                        settings.as_ref(),
+                        packet_version,
+                    );
+                }
+
+                result
+            }
+            TcpContract::IntermediaryConfirm {
+                packet_version,
+                topic_id,
+                queue_id,
+                confirmation_id,
+                delivered,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(INTERMEDIARY_CONFIRM);
+                result.push(packet_version);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+                crate::tcp_serializers::pascal_string::serialize(&mut result, queue_id.as_str());
+                crate::tcp_serializers::i64::serialize(&mut result, confirmation_id);
+
+                crate::tcp_serializers::queue_with_intervals::serialize(&mut result, &delivered);
+                result
+            }
+            TcpContract::PacketVersions { packet_versions } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(PACKET_VERSIONS);
+
+                let data_len = packet_versions.len() as u8;
+                crate::tcp_serializers::byte::serialize(&mut result, data_len);
+
+                for kv in packet_versions {
+                    crate::tcp_serializers::byte::serialize(&mut result, kv.0);
+                    crate::tcp_serializers::i32::serialize(&mut result, kv.1);
+                }
+                result
+            }
+            TcpContract::Reject { message } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(REJECT);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, message.as_str());
+                result
+            }
+            TcpContract::AllMessagesConfirmedAsFail {
+                topic_id,
+                queue_id,
+                confirmation_id,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(ALL_MESSAGES_NOT_DELIVERED_CONFIRMATION);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+                crate::tcp_serializers::pascal_string::serialize(&mut result, queue_id.as_str());
+                crate::tcp_serializers::i64::serialize(&mut result, confirmation_id);
+                result
+            }
+
+            TcpContract::ConfirmSomeMessagesAsOk {
+                packet_version,
+                topic_id,
+                queue_id,
+                confirmation_id,
+                delivered,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(CONFIRM_SOME_MESSAGES_AS_OK);
+                result.push(packet_version);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+                crate::tcp_serializers::pascal_string::serialize(&mut result, queue_id.as_str());
+                crate::tcp_serializers::i64::serialize(&mut result, confirmation_id);
+                crate::tcp_serializers::queue_with_intervals::serialize(&mut result, &delivered);
+                result
+            }
+
+            TcpContract::ConfirmSomeMessagesAsFail {
+                packet_version,
+                topic_id,
+                queue_id,
+                confirmation_id,
+                failed,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(CONFIRM_SOME_MESSAGES_AS_FAIL);
+                result.push(packet_version);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+                crate::tcp_serializers::pascal_string::serialize(&mut result, queue_id.as_str());
+                crate::tcp_serializers::i64::serialize(&mut result, confirmation_id);
+                crate::tcp_serializers::failed_messages::serialize(&mut result, &failed);
+                result
+            }
+
+            TcpContract::ExtendDeliveryLease {
+                packet_version,
+                topic_id,
+                queue_id,
+                confirmation_id,
+                ranges,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(EXTEND_DELIVERY_LEASE);
+                result.push(packet_version);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+                crate::tcp_serializers::pascal_string::serialize(&mut result, queue_id.as_str());
+                crate::tcp_serializers::i64::serialize(&mut result, confirmation_id);
+
+                match ranges {
+                    Some(ranges) => {
+                        crate::tcp_serializers::bool::serialize(&mut result, true);
+                        crate::tcp_serializers::queue_with_intervals::serialize(
+                            &mut result,
+                            &ranges,
+                        );
+                    }
+                    None => {
+                        crate::tcp_serializers::bool::serialize(&mut result, false);
+                    }
+                }
+
+                result
+            }
+
+            TcpContract::GrantCredits {
+                topic_id,
+                queue_id,
+                messages,
+                bytes,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(GRANT_CREDITS);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+                crate::tcp_serializers::pascal_string::serialize(&mut result, queue_id.as_str());
+                crate::tcp_serializers::i32::serialize(&mut result, messages);
+                crate::tcp_serializers::i64::serialize(&mut result, bytes);
+                result
+            }
+
+            TcpContract::ThrottlePublish {
+                topic_id,
+                max_messages_per_second,
+                pause,
+            } => {
+                //Peer which did not declare the packet would drop the connection on unknown packet id
+                if !attr.is_packet_supported(THROTTLE_PUBLISH) {
+                    return Vec::new();
+                }
+
+                let mut result: Vec<u8> = Vec::new();
+                result.push(THROTTLE_PUBLISH);
+                crate::tcp_serializers::optional_pascal_string::serialize(
+                    &mut result,
+                    topic_id.as_deref(),
+                );
+
+                match max_messages_per_second {
+                    Some(max_messages_per_second) => {
+                        crate::tcp_serializers::bool::serialize(&mut result, true);
+                        crate::tcp_serializers::i32::serialize(
+                            &mut result,
+                            max_messages_per_second,
+                        );
+                    }
+                    None => {
+                        crate::tcp_serializers::bool::serialize(&mut result, false);
+                    }
+                }
+
+                match pause {
+                    Some(pause) => {
+                        crate::tcp_serializers::bool::serialize(&mut result, true);
+                        crate::tcp_serializers::duration::serialize(&mut result, pause);
+                    }
+                    None => {
+                        crate::tcp_serializers::bool::serialize(&mut result, false);
+                    }
+                }
+
+                result
+            }
+
+            TcpContract::ResumePublish { topic_id } => {
+                //Resume is a pair of Throttle - so it's sent to the same peers
+                if !attr.is_packet_supported(THROTTLE_PUBLISH) {
+                    return Vec::new();
+                }
+
+                let mut result: Vec<u8> = Vec::new();
+                result.push(RESUME_PUBLISH);
+                crate::tcp_serializers::optional_pascal_string::serialize(
+                    &mut result,
+                    topic_id.as_deref(),
+                );
+                result
+            }
+
+            TcpContract::Unsubscribe { topic_id, queue_id } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(UNSUBSCRIBE);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+                crate::tcp_serializers::pascal_string::serialize(&mut result, queue_id.as_str());
+                result
+            }
+
+            TcpContract::UnsubscribeResponse {
+                topic_id,
+                queue_id,
+                cancelled_confirmation_ids,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(UNSUBSCRIBE_RESPONSE);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+                crate::tcp_serializers::pascal_string::serialize(&mut result, queue_id.as_str());
+
+                crate::tcp_serializers::i32::serialize(
+                    &mut result,
+                    cancelled_confirmation_ids.len() as i32,
+                );
+
+                for confirmation_id in cancelled_confirmation_ids {
+                    crate::tcp_serializers::i64::serialize(&mut result, confirmation_id);
+                }
+
+                result
+            }
+
+            TcpContract::SubscribeByPattern {
+                topic_pattern,
+                queue_id,
+                queue_type,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(SUBSCRIBE_BY_PATTERN);
+                crate::tcp_serializers::pascal_string::serialize(
+                    &mut result,
+                    topic_pattern.as_str(),
+                );
+                crate::tcp_serializers::pascal_string::serialize(&mut result, queue_id.as_str());
+                crate::tcp_serializers::byte::serialize(&mut result, queue_type.into_u8());
+                result
+            }
+
+            TcpContract::PatternTopicSubscribed {
+                topic_pattern,
+                topic_id,
+                queue_id,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(PATTERN_TOPIC_SUBSCRIBED);
+                crate::tcp_serializers::pascal_string::serialize(
+                    &mut result,
+                    topic_pattern.as_str(),
+                );
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+                crate::tcp_serializers::pascal_string::serialize(&mut result, queue_id.as_str());
+                result
+            }
+
+            TcpContract::FetchMessages {
+                request_id,
+                topic_id,
+                queue_id,
+                max_messages,
+                max_bytes,
+                max_wait,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(FETCH_MESSAGES);
+                crate::tcp_serializers::i64::serialize(&mut result, request_id);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+                crate::tcp_serializers::pascal_string::serialize(&mut result, queue_id.as_str());
+                crate::tcp_serializers::i32::serialize(&mut result, max_messages);
+                crate::tcp_serializers::i64::serialize(&mut result, max_bytes);
+                crate::tcp_serializers::duration::serialize(&mut result, max_wait);
+                result
+            }
+
+            TcpContract::FetchMessagesResponse {
+                request_id,
+                topic_id,
+                queue_id,
+                confirmation_id,
+                messages,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(FETCH_MESSAGES_RESPONSE);
+                crate::tcp_serializers::i64::serialize(&mut result, request_id);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+                crate::tcp_serializers::pascal_string::serialize(&mut result, queue_id.as_str());
+                crate::tcp_serializers::i64::serialize(&mut result, confirmation_id);
+                crate::tcp_serializers::messages_to_deliver::serialize_list(
+                    &mut result,
+                    &messages,
+                    &attr.get(NEW_MESSAGES),
+                );
+                result
+            }
+
+            TcpContract::PeekMessages {
+                request_id,
+                topic_id,
+                queue_id,
+                from_id,
+                max_count,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(PEEK_MESSAGES);
+                crate::tcp_serializers::i64::serialize(&mut result, request_id);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+                crate::tcp_serializers::pascal_string::serialize(&mut result, queue_id.as_str());
+                crate::tcp_serializers::i64::serialize(&mut result, from_id);
+                crate::tcp_serializers::i32::serialize(&mut result, max_count);
+                result
+            }
+
+            TcpContract::PeekMessagesResponse {
+                request_id,
+                topic_id,
+                queue_id,
+                messages,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(PEEK_MESSAGES_RESPONSE);
+                crate::tcp_serializers::i64::serialize(&mut result, request_id);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+                crate::tcp_serializers::pascal_string::serialize(&mut result, queue_id.as_str());
+                crate::tcp_serializers::messages_to_deliver::serialize_list(
+                    &mut result,
+                    &messages,
+                    &attr.get(NEW_MESSAGES),
+                );
+                result
+            }
+
+            TcpContract::DeclareQueue {
+                request_id,
+                topic_id,
+                queue_id,
+                queue_type,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(DECLARE_QUEUE);
+                crate::tcp_serializers::i64::serialize(&mut result, request_id);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
                 crate::tcp_serializers::pascal_string::serialize(&mut result, queue_id.as_str());
                 crate::tcp_serializers::byte::serialize(&mut result, queue_type.into_u8());
                 result
             }
-            TcpContract::SubscribeResponse { topic_id, queue_id } => {
-                let mut result: Vec<u8> = Vec::new();
-                result.push(SUBSCRIBE_RESPONSE);
-                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
-                crate::tcp_serializers::pascal_string::serialize(&mut result, queue_id.as_str());
-                result
+
+            TcpContract::DeleteQueue {
+                request_id,
+                topic_id,
+                queue_id,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(DELETE_QUEUE);
+                crate::tcp_serializers::i64::serialize(&mut result, request_id);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+                crate::tcp_serializers::pascal_string::serialize(&mut result, queue_id.as_str());
+                result
+            }
+
+            TcpContract::DeleteTopic {
+                request_id,
+                topic_id,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(DELETE_TOPIC);
+                crate::tcp_serializers::i64::serialize(&mut result, request_id);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+                result
+            }
+
+            TcpContract::PurgeQueue {
+                request_id,
+                topic_id,
+                queue_id,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(PURGE_QUEUE);
+                crate::tcp_serializers::i64::serialize(&mut result, request_id);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+                crate::tcp_serializers::pascal_string::serialize(&mut result, queue_id.as_str());
+                result
+            }
+
+            TcpContract::ManagementResponse {
+                request_id,
+                status,
+                error,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(MANAGEMENT_RESPONSE);
+                crate::tcp_serializers::i64::serialize(&mut result, request_id);
+                crate::tcp_serializers::byte::serialize(&mut result, status.into_u8());
+                crate::tcp_serializers::optional_pascal_string::serialize(
+                    &mut result,
+                    error.as_deref(),
+                );
+                result
+            }
+
+            TcpContract::CreateTopicIfNotExistsResponse {
+                topic_id,
+                status,
+                error,
+                settings,
+            } => {
+                //Peers with CREATE_TOPIC_IF_NOT_EXISTS v0 do not know the response packet
+                if !attr.is_packet_supported(CREATE_TOPIC_IF_NOT_EXISTS) {
+                    return Vec::new();
+                }
+
+                let mut result: Vec<u8> = Vec::new();
+                result.push(CREATE_TOPIC_IF_NOT_EXISTS_RESPONSE);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+                crate::tcp_serializers::byte::serialize(&mut result, status.into_u8());
+                crate::tcp_serializers::optional_pascal_string::serialize(
+                    &mut result,
+                    error.as_deref(),
+                );
+                crate::tcp_serializers::topic_settings::serialize(
+                    &mut result,
+                    settings.as_ref(),
+                    attr.get_packet_version(CREATE_TOPIC_IF_NOT_EXISTS),
+                );
+                result
+            }
+
+            TcpContract::GetQueueStats {
+                request_id,
+                topic_id,
+                queue_ids,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(GET_QUEUE_STATS);
+                crate::tcp_serializers::i64::serialize(&mut result, request_id);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+                crate::tcp_serializers::list_of_pascal_strings::serialize(&mut result, &queue_ids);
+                result
+            }
+
+            TcpContract::GetQueueStatsResponse {
+                request_id,
+                topic_id,
+                status,
+                queues,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(GET_QUEUE_STATS_RESPONSE);
+                crate::tcp_serializers::i64::serialize(&mut result, request_id);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+                crate::tcp_serializers::byte::serialize(&mut result, status.into_u8());
+                crate::tcp_serializers::queue_stats::serialize(&mut result, &queues);
+                result
+            }
+
+            TcpContract::ListTopics {
+                request_id,
+                page_token,
+                max_count,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(LIST_TOPICS);
+                crate::tcp_serializers::i64::serialize(&mut result, request_id);
+                crate::tcp_serializers::optional_pascal_string::serialize(
+                    &mut result,
+                    page_token.as_deref(),
+                );
+                crate::tcp_serializers::i32::serialize(&mut result, max_count);
+                result
+            }
+
+            TcpContract::ListTopicsResponse {
+                request_id,
+                topic_ids,
+                next_page_token,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(LIST_TOPICS_RESPONSE);
+                crate::tcp_serializers::i64::serialize(&mut result, request_id);
+                crate::tcp_serializers::list_of_pascal_strings::serialize(&mut result, &topic_ids);
+                crate::tcp_serializers::optional_pascal_string::serialize(
+                    &mut result,
+                    next_page_token.as_deref(),
+                );
+                result
+            }
+
+            TcpContract::ListQueues {
+                request_id,
+                topic_id,
+                page_token,
+                max_count,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(LIST_QUEUES);
+                crate::tcp_serializers::i64::serialize(&mut result, request_id);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+                crate::tcp_serializers::optional_pascal_string::serialize(
+                    &mut result,
+                    page_token.as_deref(),
+                );
+                crate::tcp_serializers::i32::serialize(&mut result, max_count);
+                result
+            }
+
+            TcpContract::ListQueuesResponse {
+                request_id,
+                topic_id,
+                status,
+                queues,
+                next_page_token,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(LIST_QUEUES_RESPONSE);
+                crate::tcp_serializers::i64::serialize(&mut result, request_id);
+                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
+                crate::tcp_serializers::byte::serialize(&mut result, status.into_u8());
+                crate::tcp_serializers::queue_info::serialize(&mut result, &queues);
+                crate::tcp_serializers::optional_pascal_string::serialize(
+                    &mut result,
+                    next_page_token.as_deref(),
+                );
+                result
+            }
+
+            TcpContract::ListConnections {
+                request_id,
+                page_token,
+                max_count,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(LIST_CONNECTIONS);
+                crate::tcp_serializers::i64::serialize(&mut result, request_id);
+                crate::tcp_serializers::optional_pascal_string::serialize(
+                    &mut result,
+                    page_token.as_deref(),
+                );
+                crate::tcp_serializers::i32::serialize(&mut result, max_count);
+                result
+            }
+
+            TcpContract::ListConnectionsResponse {
+                request_id,
+                connections,
+                next_page_token,
+            } => {
+                let mut result: Vec<u8> = Vec::new();
+                result.push(LIST_CONNECTIONS_RESPONSE);
+                crate::tcp_serializers::i64::serialize(&mut result, request_id);
+                crate::tcp_serializers::connection_info::serialize(
+                    &mut result,
+                    &connections,
+                    attr.get_packet_version(LIST_CONNECTIONS_RESPONSE),
+                );
+                crate::tcp_serializers::optional_pascal_string::serialize(
+                    &mut result,
+                    next_page_token.as_deref(),
+                );
+                result
+            }
+        }
+    }
+
+    pub fn compile_publish_payload(
+        topic_id: &str,
+        request_id: i64,
+        data_to_publish: &[MessageToPublish],
+        persist_immediately: bool,
+        protocol_version: i32,
+    ) -> Vec<u8> {
+        let mut result: Vec<u8> = Vec::new();
+        result.push(PUBLISH);
+        crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id);
+        crate::tcp_serializers::i64::serialize(&mut result, request_id);
+        crate::tcp_serializers::messages_to_publish::serialize(
+            &mut result,
+            &data_to_publish,
+            protocol_version,
+        );
+
+        crate::tcp_serializers::bool::serialize(&mut result, persist_immediately);
+        result
+    }
+
+    //Options are written only if other side supports Publish packet version 1+.
+    //Options are checked even if they are not written - so invalid Publish fails regardless of the peer.
+    //Options which can not be encoded by the packet version are rejected as well
+    pub fn compile_publish_payload_with_options(
+        topic_id: &str,
+        request_id: i64,
+        data_to_publish: &[MessageToPublish],
+        persist_immediately: bool,
+        expiration: Option<&MessageExpiration>,
+        options: &[PublishMessageOptions],
+        version: &PacketProtVer,
+    ) -> Result<Vec<u8>, PayloadError> {
+        crate::tcp_serializers::messages_to_publish::check_options_amount(
+            options.len() as i32,
+            data_to_publish.len(),
+        )?;
+
+        for item in options {
+            item.validate(expiration)?;
+        }
+
+        Self::compile_checked_publish_payload(
+            topic_id,
+            request_id,
+            data_to_publish,
+            persist_immediately,
+            expiration,
+            options,
+            version,
+        )
+    }
+
+    //Options are already validated: either by compile_publish_payload_with_options or by PublishMessages.
+    //Only packet version is checked here
+    fn compile_checked_publish_payload(
+        topic_id: &str,
+        request_id: i64,
+        data_to_publish: &[MessageToPublish],
+        persist_immediately: bool,
+        expiration: Option<&MessageExpiration>,
+        options: &[PublishMessageOptions],
+        version: &PacketProtVer,
+    ) -> Result<Vec<u8>, PayloadError> {
+        for item in options {
+            item.check_packet_version(version.packet_version)?;
+        }
+
+        let mut result = Self::compile_publish_payload(
+            topic_id,
+            request_id,
+            data_to_publish,
+            persist_immediately,
+            version.protocol_version,
+        );
+
+        if version.packet_version >= 1 {
+            crate::tcp_serializers::message_expiration::serialize(&mut result, expiration);
+            crate::tcp_serializers::messages_to_publish::serialize_options(
+                &mut result,
+                options,
+                version.packet_version,
+            )?;
+        }
+
+        Ok(result)
+    }
+}
+
+impl my_tcp_sockets::tcp_connection::TcpContract for TcpContract {
+    fn is_pong(&self) -> bool {
+        if let TcpContract::Pong = self {
+            return true;
+        }
+
+        false
+    }
+}
+#[cfg(test)]
+mod tests {
+
+    use my_service_bus_abstractions::MySbMessage;
+    use my_tcp_sockets::socket_reader::SocketReaderInMem;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_ping_packet() {
+        let tcp_packet = TcpContract::Ping;
+
+        let attr = ConnectionAttributes::new(0);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::Ping => {}
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pong_packet() {
+        let tcp_packet = TcpContract::Pong;
+
+        let attr = ConnectionAttributes::new(0);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::Pong => {}
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_greeting_packet() {
+        let test_app_name = "test_app";
+        let test_protocol_version = 2;
+
+        let tcp_packet = TcpContract::Greeting {
+            name: test_app_name.to_string(),
+            protocol_version: test_protocol_version,
+        };
+        let attr = ConnectionAttributes::new(0);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::Greeting {
+                name,
+                protocol_version,
+            } => {
+                assert_eq!(test_app_name, name);
+                assert_eq!(test_protocol_version, protocol_version);
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_publish_packet_v2() {
+        const PROTOCOL_VERSION: i32 = 2;
+
+        let request_id_test = 1;
+
+        let message_to_publish = MessageToPublish {
+            content: vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 0],
+            headers: None,
+        };
+
+        let data_test = vec![message_to_publish];
+        let topic_test = String::from("test-topic");
+        let persist_test = true;
+
+        let tcp_packet = TcpContract::Publish {
+            data_to_publish: PublishMessages::new(data_test),
+            persist_immediately: persist_test,
+            request_id: request_id_test,
+            topic_id: topic_test,
+        };
+        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::Publish {
+                data_to_publish,
+                persist_immediately,
+                request_id,
+                topic_id,
+                ..
+            } => {
+                assert_eq!(request_id_test, request_id);
+                assert_eq!(String::from("test-topic"), topic_id);
+                assert_eq!(persist_test, persist_immediately);
+
+                let data_test = vec![vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 0]];
+
+                let data_to_publish = data_to_publish.into_messages();
+
+                for index in 0..data_to_publish[0].content.len() {
+                    assert_eq!(data_test[0][index], data_to_publish[0].content[index]);
+                }
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_publish_packet_v3() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let request_id_test = 1;
+
+        let mut headers = HashMap::new();
+        headers.insert("key1".to_string(), "value1".to_string());
+        headers.insert("key2".to_string(), "value2".to_string());
+
+        let message_to_publish = MessageToPublish {
+            content: vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 0],
+            headers: Some(headers),
+        };
+
+        let data_test = vec![message_to_publish];
+        let topic_test = String::from("test-topic");
+        let persist_test = true;
+
+        let tcp_packet = TcpContract::Publish {
+            data_to_publish: PublishMessages::new(data_test),
+            persist_immediately: persist_test,
+            request_id: request_id_test,
+            topic_id: topic_test,
+        };
+
+        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::Publish {
+                data_to_publish,
+                persist_immediately,
+                request_id,
+                topic_id,
+                ..
+            } => {
+                assert_eq!(request_id_test, request_id);
+                assert_eq!(String::from("test-topic"), topic_id);
+                assert_eq!(persist_test, persist_immediately);
+
+                let data_test = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 0];
+
+                assert_eq!(1, data_to_publish.len());
+
+                let el0 = data_to_publish.into_messages().remove(0);
+
+                let mut headers = el0.headers.unwrap();
+
+                assert_eq!(data_test, el0.content);
+                assert_eq!(2, headers.len());
+
+                assert_eq!("value1", headers.remove("key1").unwrap());
+                assert_eq!("value2", headers.remove("key2").unwrap());
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_publish_response_packet() {
+        const PROTOCOL_VERSION: i32 = 2;
+
+        let request_id_test = 1;
+
+        let tcp_packet = TcpContract::PublishResponse {
+            request_id: request_id_test,
+        };
+
+        let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        attr.protocol_version = PROTOCOL_VERSION;
+
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::PublishResponse { request_id } => {
+                assert_eq!(request_id_test, request_id);
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_packet() {
+        const PROTOCOL_VERSION: i32 = 2;
+        let queue_id_test = String::from("queue");
+        let topic_id_test = String::from("topic");
+        let queue_type_test = TopicQueueType::PermanentWithSingleConnection;
+
+        let tcp_packet = TcpContract::Subscribe {
+            queue_id: queue_id_test,
+            topic_id: topic_id_test,
+            queue_type: queue_type_test,
+            prefetch: None,
+            start_position: SubscribeStartPosition::ServerDefault,
+            filter: None,
+        };
+
+        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::Subscribe {
+                queue_id,
+                queue_type,
+                topic_id,
+                prefetch,
+                start_position,
+                filter,
+            } => {
+                let queue_id_test = String::from("queue");
+                let topic_id_test = String::from("topic");
+
+                assert_eq!(queue_id_test, queue_id);
+                assert_eq!(topic_id_test, topic_id);
+                assert!(prefetch.is_none());
+                assert_eq!(SubscribeStartPosition::ServerDefault, start_position);
+                assert!(filter.is_none());
+                match queue_type {
+                    TopicQueueType::PermanentWithSingleConnection => {}
+                    _ => {
+                        panic!("Invalid Queue Type");
+                    }
+                };
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_confirm_some_messages_as_fail_packet() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::ConfirmSomeMessagesAsFail {
+            packet_version: 0,
+            topic_id: "topic".to_string(),
+            queue_id: "queue".to_string(),
+            confirmation_id: 15,
+            failed: vec![
+                FailedMessages {
+                    ranges: vec![QueueIndexRange {
+                        from_id: 1,
+                        to_id: 3,
+                    }],
+                    reason_code: 503,
+                    reason: "Database is not available".to_string(),
+                    redelivery_delay: std::time::Duration::from_secs(5),
+                    poison: false,
+                },
+                FailedMessages {
+                    ranges: vec![QueueIndexRange {
+                        from_id: 5,
+                        to_id: 5,
+                    }],
+                    reason_code: 400,
+                    reason: "Invalid payload".to_string(),
+                    redelivery_delay: std::time::Duration::from_secs(0),
+                    poison: true,
+                },
+            ],
+        };
+
+        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::ConfirmSomeMessagesAsFail {
+                packet_version,
+                topic_id,
+                queue_id,
+                confirmation_id,
+                failed,
+            } => {
+                assert_eq!(0, packet_version);
+                assert_eq!("topic", topic_id);
+                assert_eq!("queue", queue_id);
+                assert_eq!(15, confirmation_id);
+                assert_eq!(2, failed.len());
+
+                assert_eq!(1, failed[0].ranges[0].from_id);
+                assert_eq!(3, failed[0].ranges[0].to_id);
+                assert_eq!(503, failed[0].reason_code);
+                assert_eq!("Database is not available", failed[0].reason);
+                assert_eq!(
+                    std::time::Duration::from_secs(5),
+                    failed[0].redelivery_delay
+                );
+                assert!(!failed[0].poison);
+
+                assert_eq!(5, failed[1].ranges[0].from_id);
+                assert_eq!("Invalid payload", failed[1].reason);
+                assert!(failed[1].poison);
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_confirm_some_messages_as_fail_packet_strict_ranges() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::ConfirmSomeMessagesAsFail {
+            packet_version: 0,
+            topic_id: "topic".to_string(),
+            queue_id: "queue".to_string(),
+            confirmation_id: 15,
+            failed: vec![FailedMessages {
+                ranges: vec![
+                    QueueIndexRange {
+                        from_id: 5,
+                        to_id: 7,
+                    },
+                    QueueIndexRange {
+                        from_id: 1,
+                        to_id: 3,
+                    },
+                ],
+                reason_code: 503,
+                reason: "Database is not available".to_string(),
+                redelivery_delay: std::time::Duration::from_secs(5),
+                poison: false,
+            }],
+        };
+
+        let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data.clone());
+        let result = TcpContract::deserialize(&mut socket_reader, &attr).await;
+        assert!(result.is_ok());
+
+        attr.strict_queue_ranges = true;
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+        let result = TcpContract::deserialize(&mut socket_reader, &attr).await;
+        assert!(matches!(
+            result,
+            Err(TcpContractReadFail::InvalidPayload(
+                PayloadError::InvalidQueueIndexRanges(_)
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_extend_delivery_lease_packet() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::ExtendDeliveryLease {
+            packet_version: 0,
+            topic_id: "topic".to_string(),
+            queue_id: "queue".to_string(),
+            confirmation_id: 15,
+            ranges: Some(vec![QueueIndexRange {
+                from_id: 10,
+                to_id: 20,
+            }]),
+        };
+
+        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::ExtendDeliveryLease {
+                topic_id,
+                queue_id,
+                confirmation_id,
+                ranges,
+                ..
+            } => {
+                assert_eq!("topic", topic_id);
+                assert_eq!("queue", queue_id);
+                assert_eq!(15, confirmation_id);
+
+                let ranges = ranges.unwrap();
+                assert_eq!(1, ranges.len());
+                assert_eq!(10, ranges[0].from_id);
+                assert_eq!(20, ranges[0].to_id);
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_extend_delivery_lease_packet_for_whole_delivery() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::ExtendDeliveryLease {
+            packet_version: 0,
+            topic_id: "topic".to_string(),
+            queue_id: "queue".to_string(),
+            confirmation_id: 15,
+            ranges: None,
+        };
+
+        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::ExtendDeliveryLease {
+                confirmation_id,
+                ranges,
+                ..
+            } => {
+                assert_eq!(15, confirmation_id);
+                assert!(ranges.is_none());
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_packet_with_prefetch() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::Subscribe {
+            queue_id: "queue".to_string(),
+            topic_id: "topic".to_string(),
+            queue_type: TopicQueueType::Permanent,
+            prefetch: Some(PrefetchLimit {
+                max_messages: 100,
+                max_bytes: 1024 * 1024,
+            }),
+            start_position: SubscribeStartPosition::ServerDefault,
+            filter: None,
+        };
+
+        let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        attr.versions.set_packet_version(SUBSCRIBE, 1);
+
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::Subscribe { prefetch, .. } => {
+                let prefetch = prefetch.unwrap();
+                assert_eq!(100, prefetch.max_messages);
+                assert_eq!(1024 * 1024, prefetch.max_bytes);
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_grant_credits_packet() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::GrantCredits {
+            topic_id: "topic".to_string(),
+            queue_id: "queue".to_string(),
+            messages: 10,
+            bytes: 4096,
+        };
+
+        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::GrantCredits {
+                topic_id,
+                queue_id,
+                messages,
+                bytes,
+            } => {
+                assert_eq!("topic", topic_id);
+                assert_eq!("queue", queue_id);
+                assert_eq!(10, messages);
+                assert_eq!(4096, bytes);
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_throttle_publish_packet() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::ThrottlePublish {
+            topic_id: Some("topic".to_string()),
+            max_messages_per_second: Some(1000),
+            pause: None,
+        };
+
+        let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        attr.versions.set_packet_version(THROTTLE_PUBLISH, 1);
+
+        assert!(attr.is_packet_supported(THROTTLE_PUBLISH));
+
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::ThrottlePublish {
+                topic_id,
+                max_messages_per_second,
+                pause,
+            } => {
+                assert_eq!("topic", topic_id.unwrap());
+                assert_eq!(1000, max_messages_per_second.unwrap());
+                assert!(pause.is_none());
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resume_publish_packet() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::ResumePublish { topic_id: None };
+
+        let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        attr.versions.set_packet_version(THROTTLE_PUBLISH, 1);
+
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::ResumePublish { topic_id } => {
+                assert!(topic_id.is_none());
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_throttle_and_resume_publish_are_not_sent_to_unsupported_peer() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+
+        assert!(!attr.is_packet_supported(THROTTLE_PUBLISH));
+
+        let tcp_packet = TcpContract::ThrottlePublish {
+            topic_id: None,
+            max_messages_per_second: None,
+            pause: Some(std::time::Duration::from_secs(1)),
+        };
+        assert!(tcp_packet.serialize_with_attr(&attr).is_empty());
+
+        let tcp_packet = TcpContract::ResumePublish { topic_id: None };
+        assert!(tcp_packet.serialize_with_attr(&attr).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unsubscribe_packet() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::Unsubscribe {
+            topic_id: "topic".to_string(),
+            queue_id: "queue".to_string(),
+        };
+
+        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::Unsubscribe { topic_id, queue_id } => {
+                assert_eq!("topic", topic_id);
+                assert_eq!("queue", queue_id);
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unsubscribe_response_packet() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::UnsubscribeResponse {
+            topic_id: "topic".to_string(),
+            queue_id: "queue".to_string(),
+            cancelled_confirmation_ids: vec![5, 6],
+        };
+
+        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::UnsubscribeResponse {
+                topic_id,
+                queue_id,
+                cancelled_confirmation_ids,
+            } => {
+                assert_eq!("topic", topic_id);
+                assert_eq!("queue", queue_id);
+                assert_eq!(vec![5, 6], cancelled_confirmation_ids);
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_response_packet_v1() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::SubscribeResponse {
+            topic_id: "topic".to_string(),
+            queue_id: "queue".to_string(),
+            details: Some(SubscribeResponseDetails {
+                status: crate::SubscribeStatus::Ok,
+                error: None,
+                first_message_id: Some(10),
+                last_message_id: Some(20),
+                queue_type: TopicQueueType::DeleteOnDisconnect,
+            }),
+        };
+
+        let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        attr.versions.set_packet_version(SUBSCRIBE_RESPONSE, 1);
+
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::SubscribeResponse {
+                topic_id,
+                queue_id,
+                details,
+            } => {
+                assert_eq!("topic", topic_id);
+                assert_eq!("queue", queue_id);
+
+                let details = details.unwrap();
+                assert_eq!(crate::SubscribeStatus::Ok, details.status);
+                assert!(details.error.is_none());
+                assert_eq!(Some(10), details.first_message_id);
+                assert_eq!(Some(20), details.last_message_id);
+
+                match details.queue_type {
+                    TopicQueueType::DeleteOnDisconnect => {}
+                    _ => {
+                        panic!("Invalid Queue Type");
+                    }
+                }
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_response_packet_legacy() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::SubscribeResponse {
+            topic_id: "topic".to_string(),
+            queue_id: "queue".to_string(),
+            details: Some(SubscribeResponseDetails {
+                status: crate::SubscribeStatus::TopicNotFound,
+                error: Some("Topic not found".to_string()),
+                first_message_id: None,
+                last_message_id: None,
+                queue_type: TopicQueueType::Permanent,
+            }),
+        };
+
+        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::SubscribeResponse {
+                topic_id,
+                queue_id,
+                details,
+            } => {
+                assert_eq!("topic", topic_id);
+                assert_eq!("queue", queue_id);
+                assert!(details.is_none());
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_packet_with_start_position() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::Subscribe {
+            queue_id: "queue".to_string(),
+            topic_id: "topic".to_string(),
+            queue_type: TopicQueueType::Permanent,
+            prefetch: None,
+            start_position: SubscribeStartPosition::FromMessageId(150),
+            filter: None,
+        };
+
+        let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        attr.versions.set_packet_version(SUBSCRIBE, 2);
+
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::Subscribe {
+                prefetch,
+                start_position,
+                ..
+            } => {
+                assert!(prefetch.is_none());
+                assert_eq!(SubscribeStartPosition::FromMessageId(150), start_position);
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_packet_with_filter() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let filter = HeaderFilter::Equals {
+            key: "type".to_string(),
+            value: "OrderCreated".to_string(),
+        };
+
+        let tcp_packet = TcpContract::Subscribe {
+            queue_id: "queue".to_string(),
+            topic_id: "topic".to_string(),
+            queue_type: TopicQueueType::Permanent,
+            prefetch: None,
+            start_position: SubscribeStartPosition::Latest,
+            filter: Some(filter.clone()),
+        };
+
+        let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        attr.versions.set_packet_version(SUBSCRIBE, 3);
+
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::Subscribe {
+                start_position,
+                filter: result_filter,
+                ..
+            } => {
+                assert_eq!(SubscribeStartPosition::Latest, start_position);
+                assert_eq!(Some(filter), result_filter);
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_by_pattern_packet() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::SubscribeByPattern {
+            topic_pattern: "orders.*".to_string(),
+            queue_id: "queue".to_string(),
+            queue_type: TopicQueueType::DeleteOnDisconnect,
+        };
+
+        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::SubscribeByPattern {
+                topic_pattern,
+                queue_id,
+                queue_type,
+            } => {
+                assert_eq!("orders.*", topic_pattern);
+                assert_eq!("queue", queue_id);
+
+                match queue_type {
+                    TopicQueueType::DeleteOnDisconnect => {}
+                    _ => {
+                        panic!("Invalid Queue Type");
+                    }
+                }
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pattern_topic_subscribed_packet() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::PatternTopicSubscribed {
+            topic_pattern: "orders.*".to_string(),
+            topic_id: "orders.created".to_string(),
+            queue_id: "queue".to_string(),
+        };
+
+        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::PatternTopicSubscribed {
+                topic_pattern,
+                topic_id,
+                queue_id,
+            } => {
+                assert_eq!("orders.*", topic_pattern);
+                assert_eq!("orders.created", topic_id);
+                assert_eq!("queue", queue_id);
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pause_subscription_packet() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::PauseSubscription {
+            topic_id: "topic".to_string(),
+            queue_id: "queue".to_string(),
+        };
+
+        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::PauseSubscription { topic_id, queue_id } => {
+                assert_eq!("topic", topic_id);
+                assert_eq!("queue", queue_id);
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resume_subscription_packet() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::ResumeSubscription {
+            topic_id: "topic".to_string(),
+            queue_id: "queue".to_string(),
+        };
+
+        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::ResumeSubscription { topic_id, queue_id } => {
+                assert_eq!("topic", topic_id);
+                assert_eq!("queue", queue_id);
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_messages_packet() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::FetchMessages {
+            request_id: 5,
+            topic_id: "topic".to_string(),
+            queue_id: "queue".to_string(),
+            max_messages: 100,
+            max_bytes: 65536,
+            max_wait: Duration::from_secs(3),
+        };
+
+        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::FetchMessages {
+                request_id,
+                topic_id,
+                queue_id,
+                max_messages,
+                max_bytes,
+                max_wait,
+            } => {
+                assert_eq!(5, request_id);
+                assert_eq!("topic", topic_id);
+                assert_eq!("queue", queue_id);
+                assert_eq!(100, max_messages);
+                assert_eq!(65536, max_bytes);
+                assert_eq!(Duration::from_secs(3), max_wait);
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_messages_response_packet() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let mut headers = HashMap::new();
+        headers.insert("key1".to_string(), "value1".to_string());
+
+        let tcp_packet = TcpContract::FetchMessagesResponse {
+            request_id: 5,
+            topic_id: "topic".to_string(),
+            queue_id: "queue".to_string(),
+            confirmation_id: 15,
+            messages: vec![
+                DeliveredMessage {
+                    message: MySbMessage {
+                        id: 1.into(),
+                        content: vec![1, 1, 1],
+                        headers: Some(headers),
+                        attempt_no: 1,
+                    },
+                    details: crate::DeliveredMessageDetails {
+                        published: 1_000_000,
+                        expires: Some(2_000_000),
+                    },
+                },
+                DeliveredMessage::new(MySbMessage {
+                    id: 2.into(),
+                    content: vec![2, 2, 2],
+                    headers: None,
+                    attempt_no: 2,
+                }),
+            ],
+        };
+
+        let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        attr.versions.set_packet_version(NEW_MESSAGES, 4);
+
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::FetchMessagesResponse {
+                request_id,
+                confirmation_id,
+                messages,
+                ..
+            } => {
+                assert_eq!(5, request_id);
+                assert_eq!(15, confirmation_id);
+                assert_eq!(2, messages.len());
+
+                assert_eq!(1, messages[0].message.attempt_no);
+                assert_eq!(vec![1, 1, 1], messages[0].message.content);
+                assert_eq!(1, messages[0].message.headers.as_ref().unwrap().len());
+                assert_eq!(1_000_000, messages[0].details.published);
+                assert_eq!(Some(2_000_000), messages[0].details.expires);
+
+                assert_eq!(2, messages[1].message.attempt_no);
+                assert_eq!(vec![2, 2, 2], messages[1].message.content);
+                assert!(messages[1].message.headers.is_none());
+                assert_eq!(0, messages[1].details.published);
+                assert!(messages[1].details.expires.is_none());
+            }
+            _ => {
+                panic!("Invalid Packet Type");
             }
-            TcpContract::Raw(payload) => payload,
-            TcpContract::NewMessages {
-                topic_id: _,
-                queue_id: _,
-                confirmation_id: _,
-                messages: _,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_peek_messages_packet() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::PeekMessages {
+            request_id: 5,
+            topic_id: "topic".to_string(),
+            queue_id: "queue".to_string(),
+            from_id: 100,
+            max_count: 10,
+        };
+
+        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::PeekMessages {
+                request_id,
+                topic_id,
+                queue_id,
+                from_id,
+                max_count,
             } => {
-                panic!(
-                    "This packet is not used by server. Server uses optimized version of the packet"
+                assert_eq!(5, request_id);
+                assert_eq!("topic", topic_id);
+                assert_eq!("queue", queue_id);
+                assert_eq!(100, from_id);
+                assert_eq!(10, max_count);
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_peek_messages_response_packet() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::PeekMessagesResponse {
+            request_id: 5,
+            topic_id: "topic".to_string(),
+            queue_id: "queue".to_string(),
+            messages: vec![DeliveredMessage {
+                message: MySbMessage {
+                    id: 100.into(),
+                    content: vec![1, 2, 3],
+                    headers: None,
+                    attempt_no: 3,
+                },
+                details: crate::DeliveredMessageDetails {
+                    published: 1_000_000,
+                    expires: Some(2_000_000),
+                },
+            }],
+        };
+
+        //Details are not supported by NewMessages packet version 1 - so they are not delivered
+        let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        attr.versions.set_packet_version(NEW_MESSAGES, 1);
+
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::PeekMessagesResponse {
+                request_id,
+                topic_id,
+                queue_id,
+                messages,
+            } => {
+                assert_eq!(5, request_id);
+                assert_eq!("topic", topic_id);
+                assert_eq!("queue", queue_id);
+                assert_eq!(1, messages.len());
+                assert_eq!(100, messages[0].message.id.get_value());
+                assert_eq!(3, messages[0].message.attempt_no);
+                assert_eq!(vec![1, 2, 3], messages[0].message.content);
+                assert_eq!(0, messages[0].details.published);
+                assert!(messages[0].details.expires.is_none());
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_topic_if_not_exists_packet_with_settings() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::CreateTopicIfNotExists {
+            topic_id: "topic".to_string(),
+            settings: Some(TopicSettings {
+                max_messages_amount: Some(100_000),
+                persistence: crate::TopicPersistence::PersistImmediately,
+                max_bytes: Some(1024),
+                message_ttl: Some(Duration::from_secs(60)),
+                max_message_size: None,
+            }),
+        };
+
+        let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        attr.versions
+            .set_packet_version(CREATE_TOPIC_IF_NOT_EXISTS, 1);
+
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::CreateTopicIfNotExists { topic_id, settings } => {
+                assert_eq!("topic", topic_id);
+
+                //Packet version 1 carries only max messages amount and persist flag
+                let settings = settings.unwrap();
+                assert_eq!(Some(100_000), settings.max_messages_amount);
+                assert_eq!(crate::TopicPersistence::Persistent, settings.persistence);
+                assert!(settings.max_bytes.is_none());
+                assert!(settings.message_ttl.is_none());
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_declare_queue_packet() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::DeclareQueue {
+            request_id: 7,
+            topic_id: "topic".to_string(),
+            queue_id: "queue".to_string(),
+            queue_type: TopicQueueType::PermanentWithSingleConnection,
+        };
+
+        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::DeclareQueue {
+                request_id,
+                topic_id,
+                queue_id,
+                queue_type,
+            } => {
+                assert_eq!(7, request_id);
+                assert_eq!("topic", topic_id);
+                assert_eq!("queue", queue_id);
+
+                match queue_type {
+                    TopicQueueType::PermanentWithSingleConnection => {}
+                    _ => {
+                        panic!("Invalid Queue Type");
+                    }
+                }
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_management_response_packet() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::ManagementResponse {
+            request_id: 7,
+            status: ManagementStatus::QueueNotFound,
+            error: Some("Queue not found".to_string()),
+        };
+
+        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::ManagementResponse {
+                request_id,
+                status,
+                error,
+            } => {
+                assert_eq!(7, request_id);
+                assert_eq!(ManagementStatus::QueueNotFound, status);
+                assert_eq!(Some("Queue not found".to_string()), error);
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_management_response_packet_with_unknown_status() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let mut serialized_data = vec![MANAGEMENT_RESPONSE];
+        crate::tcp_serializers::i64::serialize(&mut serialized_data, 1);
+        crate::tcp_serializers::byte::serialize(&mut serialized_data, 5);
+        crate::tcp_serializers::optional_pascal_string::serialize(&mut serialized_data, None);
+
+        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr).await;
+
+        assert!(matches!(
+            result,
+            Err(TcpContractReadFail::InvalidPayload(
+                PayloadError::UnknownManagementStatus(5)
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_create_topic_if_not_exists_packet_with_settings_v2() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::CreateTopicIfNotExists {
+            topic_id: "topic".to_string(),
+            settings: Some(TopicSettings {
+                max_messages_amount: None,
+                persistence: crate::TopicPersistence::PersistImmediately,
+                max_bytes: Some(1024),
+                message_ttl: Some(Duration::from_secs(60)),
+                max_message_size: Some(512),
+            }),
+        };
+
+        let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        attr.versions
+            .set_packet_version(CREATE_TOPIC_IF_NOT_EXISTS, 2);
+
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::CreateTopicIfNotExists { topic_id, settings } => {
+                assert_eq!("topic", topic_id);
+
+                let settings = settings.unwrap();
+                assert!(settings.max_messages_amount.is_none());
+                assert_eq!(
+                    crate::TopicPersistence::PersistImmediately,
+                    settings.persistence
                 );
+                assert_eq!(Some(1024), settings.max_bytes);
+                assert_eq!(Some(Duration::from_secs(60)), settings.message_ttl);
+                assert_eq!(Some(512), settings.max_message_size);
             }
-            TcpContract::NewMessagesConfirmation {
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_topic_if_not_exists_response_packet() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::CreateTopicIfNotExistsResponse {
+            topic_id: "topic".to_string(),
+            status: ManagementStatus::Ok,
+            error: None,
+            settings: Some(TopicSettings {
+                max_messages_amount: Some(100_000),
+                persistence: crate::TopicPersistence::Persistent,
+                max_bytes: None,
+                message_ttl: Some(Duration::from_secs(3600)),
+                max_message_size: Some(4 * 1024 * 1024),
+            }),
+        };
+
+        let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        attr.versions
+            .set_packet_version(CREATE_TOPIC_IF_NOT_EXISTS, 2);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::CreateTopicIfNotExistsResponse {
+                topic_id,
+                status,
+                error,
+                settings,
+            } => {
+                assert_eq!("topic", topic_id);
+                assert_eq!(ManagementStatus::Ok, status);
+                assert!(error.is_none());
+
+                let settings = settings.unwrap();
+                assert_eq!(Some(100_000), settings.max_messages_amount);
+                assert_eq!(crate::TopicPersistence::Persistent, settings.persistence);
+                assert!(settings.max_bytes.is_none());
+                assert_eq!(Some(Duration::from_secs(3600)), settings.message_ttl);
+                assert_eq!(Some(4 * 1024 * 1024), settings.max_message_size);
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_topic_if_not_exists_response_packet_v1() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::CreateTopicIfNotExistsResponse {
+            topic_id: "topic".to_string(),
+            status: ManagementStatus::Ok,
+            error: None,
+            settings: Some(TopicSettings {
+                max_messages_amount: Some(100_000),
+                persistence: crate::TopicPersistence::Persistent,
+                max_bytes: Some(1024),
+                message_ttl: Some(Duration::from_secs(3600)),
+                max_message_size: None,
+            }),
+        };
+
+        let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        attr.versions
+            .set_packet_version(CREATE_TOPIC_IF_NOT_EXISTS, 1);
+
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::CreateTopicIfNotExistsResponse { settings, .. } => {
+                let settings = settings.unwrap();
+                assert_eq!(Some(100_000), settings.max_messages_amount);
+                assert_eq!(crate::TopicPersistence::Persistent, settings.persistence);
+                assert!(settings.max_bytes.is_none());
+                assert!(settings.message_ttl.is_none());
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_topic_if_not_exists_response_packet_rejected() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::CreateTopicIfNotExistsResponse {
+            topic_id: "topic".to_string(),
+            status: ManagementStatus::Rejected,
+            error: Some("Topics limit is reached".to_string()),
+            settings: None,
+        };
+
+        let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        attr.versions
+            .set_packet_version(CREATE_TOPIC_IF_NOT_EXISTS, 1);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::CreateTopicIfNotExistsResponse {
+                topic_id,
+                status,
+                error,
+                settings,
+            } => {
+                assert_eq!("topic", topic_id);
+                assert_eq!(ManagementStatus::Rejected, status);
+                assert_eq!("Topics limit is reached", error.unwrap());
+                assert!(settings.is_none());
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
+
+    #[test]
+    fn test_create_topic_if_not_exists_response_is_not_sent_to_v0_peer() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+
+        assert!(!attr.is_packet_supported(CREATE_TOPIC_IF_NOT_EXISTS));
+
+        let tcp_packet = TcpContract::CreateTopicIfNotExistsResponse {
+            topic_id: "topic".to_string(),
+            status: ManagementStatus::Ok,
+            error: None,
+            settings: None,
+        };
+
+        assert!(tcp_packet.serialize_with_attr(&attr).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_queue_stats_packet() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::GetQueueStats {
+            request_id: 3,
+            topic_id: "topic".to_string(),
+            queue_ids: vec!["queue1".to_string(), "queue2".to_string()],
+        };
+
+        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::GetQueueStats {
+                request_id,
                 topic_id,
-                queue_id,
-                confirmation_id,
+                queue_ids,
             } => {
-                let mut result: Vec<u8> = Vec::new();
-                result.push(ALL_MESSAGES_DELIVERED_CONFIRMATION);
-                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
-                crate::tcp_serializers::pascal_string::serialize(&mut result, queue_id.as_str());
-                crate::tcp_serializers::i64::serialize(&mut result, confirmation_id);
-                result
+                assert_eq!(3, request_id);
+                assert_eq!("topic", topic_id);
+                assert_eq!(vec!["queue1".to_string(), "queue2".to_string()], queue_ids);
             }
-            TcpContract::CreateTopicIfNotExists { topic_id } => {
-                let mut result: Vec<u8> = Vec::new();
-                result.push(CREATE_TOPIC_IF_NOT_EXISTS);
-                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
-                result
+            _ => {
+                panic!("Invalid Packet Type");
             }
-            TcpContract::IntermediaryConfirm {
-                packet_version,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_queue_stats_response_packet() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::GetQueueStatsResponse {
+            request_id: 3,
+            topic_id: "topic".to_string(),
+            status: ManagementStatus::Ok,
+            queues: vec![QueueStats {
+                queue_id: "queue".to_string(),
+                messages_amount: 15,
+                first_message_id: Some(100),
+                last_message_id: Some(114),
+                in_flight_amount: 5,
+                subscribers_amount: 2,
+                publish_messages_per_second: 30,
+            }],
+        };
+
+        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::GetQueueStatsResponse {
+                request_id,
                 topic_id,
-                queue_id,
-                confirmation_id,
-                delivered,
+                status,
+                queues,
             } => {
-                let mut result: Vec<u8> = Vec::new();
-                result.push(INTERMEDIARY_CONFIRM);
-                result.push(packet_version);
-                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
-                crate::tcp_serializers::pascal_string::serialize(&mut result, queue_id.as_str());
-                crate::tcp_serializers::i64::serialize(&mut result, confirmation_id);
+                assert_eq!(3, request_id);
+                assert_eq!("topic", topic_id);
+                assert_eq!(ManagementStatus::Ok, status);
+                assert_eq!(1, queues.len());
 
-                crate::tcp_serializers::queue_with_intervals::serialize(&mut result, &delivered);
-                result
+                let stats = &queues[0];
+                assert_eq!("queue", stats.queue_id);
+                assert_eq!(15, stats.messages_amount);
+                assert_eq!(Some(100), stats.first_message_id);
+                assert_eq!(Some(114), stats.last_message_id);
+                assert_eq!(5, stats.in_flight_amount);
+                assert_eq!(2, stats.subscribers_amount);
+                assert_eq!(30, stats.publish_messages_per_second);
             }
-            TcpContract::PacketVersions { packet_versions } => {
-                let mut result: Vec<u8> = Vec::new();
-                result.push(PACKET_VERSIONS);
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
+    }
 
-                let data_len = packet_versions.len() as u8;
-                crate::tcp_serializers::byte::serialize(&mut result, data_len);
+    #[tokio::test]
+    async fn test_list_topics_packets() {
+        const PROTOCOL_VERSION: i32 = 3;
 
-                for kv in packet_versions {
-                    crate::tcp_serializers::byte::serialize(&mut result, kv.0);
-                    crate::tcp_serializers::i32::serialize(&mut result, kv.1);
-                }
-                result
+        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+
+        let tcp_packet = TcpContract::ListTopics {
+            request_id: 1,
+            page_token: Some("topic-b".to_string()),
+            max_count: 2,
+        };
+
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::ListTopics {
+                request_id,
+                page_token,
+                max_count,
+            } => {
+                assert_eq!(1, request_id);
+                assert_eq!(Some("topic-b".to_string()), page_token);
+                assert_eq!(2, max_count);
             }
-            TcpContract::Reject { message } => {
-                let mut result: Vec<u8> = Vec::new();
-                result.push(REJECT);
-                crate::tcp_serializers::pascal_string::serialize(&mut result, message.as_str());
-                result
+            _ => {
+                panic!("Invalid Packet Type");
             }
-            TcpContract::AllMessagesConfirmedAsFail {
-                topic_id,
-                queue_id,
-                confirmation_id,
+        }
+
+        let tcp_packet = TcpContract::ListTopicsResponse {
+            request_id: 1,
+            topic_ids: vec!["topic-b".to_string(), "topic-c".to_string()],
+            next_page_token: None,
+        };
+
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::ListTopicsResponse {
+                request_id,
+                topic_ids,
+                next_page_token,
             } => {
-                let mut result: Vec<u8> = Vec::new();
-                result.push(ALL_MESSAGES_NOT_DELIVERED_CONFIRMATION);
-                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
-                crate::tcp_serializers::pascal_string::serialize(&mut result, queue_id.as_str());
-                crate::tcp_serializers::i64::serialize(&mut result, confirmation_id);
-                result
+                assert_eq!(1, request_id);
+                assert_eq!(
+                    vec!["topic-b".to_string(), "topic-c".to_string()],
+                    topic_ids
+                );
+                assert!(next_page_token.is_none());
+            }
+            _ => {
+                panic!("Invalid Packet Type");
             }
+        }
+    }
 
-            TcpContract::ConfirmSomeMessagesAsOk {
-                packet_version,
+    #[tokio::test]
+    async fn test_list_queues_response_packet() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::ListQueuesResponse {
+            request_id: 1,
+            topic_id: "topic".to_string(),
+            status: ManagementStatus::Ok,
+            queues: vec![QueueInfo {
+                queue_id: "queue".to_string(),
+                queue_type: TopicQueueType::Permanent,
+            }],
+            next_page_token: Some("queue".to_string()),
+        };
+
+        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::ListQueuesResponse {
+                request_id,
                 topic_id,
-                queue_id,
-                confirmation_id,
-                delivered,
+                status,
+                queues,
+                next_page_token,
             } => {
-                let mut result: Vec<u8> = Vec::new();
-                result.push(CONFIRM_SOME_MESSAGES_AS_OK);
-                result.push(packet_version);
-                crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id.as_str());
-                crate::tcp_serializers::pascal_string::serialize(&mut result, queue_id.as_str());
-                crate::tcp_serializers::i64::serialize(&mut result, confirmation_id);
-                crate::tcp_serializers::queue_with_intervals::serialize(&mut result, &delivered);
-                result
+                assert_eq!(1, request_id);
+                assert_eq!("topic", topic_id);
+                assert_eq!(ManagementStatus::Ok, status);
+                assert_eq!(1, queues.len());
+                assert_eq!("queue", queues[0].queue_id);
+
+                match queues[0].queue_type {
+                    TopicQueueType::Permanent => {}
+                    _ => {
+                        panic!("Invalid Queue Type");
+                    }
+                }
+
+                assert_eq!(Some("queue".to_string()), next_page_token);
+            }
+            _ => {
+                panic!("Invalid Packet Type");
             }
         }
     }
 
-    pub fn compile_publish_payload(
-        topic_id: &str,
-        request_id: i64,
-        data_to_publish: &[MessageToPublish],
-        persist_immediately: bool,
-        protocol_version: i32,
-    ) -> Vec<u8> {
-        let mut result: Vec<u8> = Vec::new();
-        result.push(PUBLISH);
-        crate::tcp_serializers::pascal_string::serialize(&mut result, topic_id);
-        crate::tcp_serializers::i64::serialize(&mut result, request_id);
-        crate::tcp_serializers::messages_to_publish::serialize(
-            &mut result,
-            &data_to_publish,
-            protocol_version,
-        );
+    #[tokio::test]
+    async fn test_list_connections_response_packet_v1() {
+        const PROTOCOL_VERSION: i32 = 3;
 
-        crate::tcp_serializers::bool::serialize(&mut result, persist_immediately);
-        result
-    }
-}
+        let mut packet_versions = HashMap::new();
+        packet_versions.insert(NEW_MESSAGES, 1);
 
-impl my_tcp_sockets::tcp_connection::TcpContract for TcpContract {
-    fn is_pong(&self) -> bool {
-        if let TcpContract::Pong = self {
-            return true;
+        let tcp_packet = TcpContract::ListConnectionsResponse {
+            request_id: 1,
+            connections: vec![ConnectionInfo {
+                connection_id: 10,
+                name: "test-app".to_string(),
+                protocol_version: 3,
+                ip: "127.0.0.1:5000".to_string(),
+                packet_versions,
+            }],
+            next_page_token: None,
+        };
+
+        let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        attr.versions
+            .set_packet_version(LIST_CONNECTIONS_RESPONSE, 1);
+
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::ListConnectionsResponse {
+                request_id,
+                connections,
+                next_page_token,
+            } => {
+                assert_eq!(1, request_id);
+                assert_eq!(1, connections.len());
+
+                let connection = &connections[0];
+                assert_eq!(10, connection.connection_id);
+                assert_eq!("test-app", connection.name);
+                assert_eq!(3, connection.protocol_version);
+                assert_eq!("127.0.0.1:5000", connection.ip);
+                assert_eq!(Some(&1), connection.packet_versions.get(&NEW_MESSAGES));
+
+                assert!(next_page_token.is_none());
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
         }
+    }
 
-        false
+    #[tokio::test]
+    async fn test_publish_packet_with_expiration() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::Publish {
+            topic_id: "topic".to_string(),
+            request_id: 1,
+            persist_immediately: false,
+            data_to_publish: PublishMessages::with_options(
+                vec![
+                    MessageToPublish {
+                        content: vec![1, 2, 3],
+                        headers: None,
+                    },
+                    MessageToPublish {
+                        content: vec![4, 5, 6],
+                        headers: None,
+                    },
+                ],
+                Some(MessageExpiration::After(Duration::from_secs(60))),
+                vec![
+                    PublishMessageOptions::default(),
+                    PublishMessageOptions {
+                        expiration: Some(MessageExpiration::At(1_000_000)),
+                        deliver_not_before: None,
+                        dedup_key: None,
+                    },
+                ],
+            )
+            .unwrap(),
+        };
+
+        let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        attr.versions.set_packet_version(PUBLISH, 1);
+
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr)
+            .await
+            .unwrap();
+
+        match result {
+            TcpContract::Publish {
+                data_to_publish, ..
+            } => {
+                assert_eq!(2, data_to_publish.len());
+                assert_eq!(
+                    Some(&MessageExpiration::After(Duration::from_secs(60))),
+                    data_to_publish.get_expiration()
+                );
+                let options = data_to_publish.get_options();
+                assert_eq!(2, options.len());
+                assert!(options[0].expiration.is_none());
+                assert_eq!(
+                    Some(MessageExpiration::At(1_000_000)),
+                    options[1].expiration
+                );
+            }
+            _ => {
+                panic!("Invalid Packet Type");
+            }
+        }
     }
-}
-#[cfg(test)]
-mod tests {
 
-    use my_tcp_sockets::socket_reader::SocketReaderInMem;
+    #[tokio::test]
+    async fn test_publish_packet_with_options_amount_mismatch() {
+        const PROTOCOL_VERSION: i32 = 3;
 
-    use super::*;
+        let data_to_publish = vec![MessageToPublish {
+            content: vec![1, 2, 3],
+            headers: None,
+        }];
+
+        let options = vec![
+            PublishMessageOptions::default(),
+            PublishMessageOptions::default(),
+        ];
+
+        let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        attr.versions.set_packet_version(PUBLISH, 1);
+
+        let result = TcpContract::compile_publish_payload_with_options(
+            "topic",
+            1,
+            &data_to_publish,
+            false,
+            None,
+            &options,
+            &attr.get(PUBLISH),
+        );
 
-    #[tokio::test]
-    async fn test_ping_packet() {
-        let tcp_packet = TcpContract::Ping;
+        assert_eq!(
+            Err(PayloadError::PublishOptionsAmountMismatch {
+                options_amount: 2,
+                messages_amount: 1,
+            }),
+            result
+        );
 
-        let serialized_data: Vec<u8> = tcp_packet.serialize(2);
+        //Other side does not trust the sender either
+        let mut serialized_data = TcpContract::compile_publish_payload(
+            "topic",
+            1,
+            &data_to_publish,
+            false,
+            PROTOCOL_VERSION,
+        );
+        crate::tcp_serializers::message_expiration::serialize(&mut serialized_data, None);
+        crate::tcp_serializers::messages_to_publish::serialize_options(
+            &mut serialized_data,
+            &options,
+            1,
+        )
+        .unwrap();
 
         let mut socket_reader = SocketReaderInMem::new(serialized_data);
-        let attr = ConnectionAttributes::new(0);
 
-        let result = TcpContract::deserialize(&mut socket_reader, &attr)
-            .await
-            .unwrap();
+        let result = TcpContract::deserialize(&mut socket_reader, &attr).await;
 
-        match result {
-            TcpContract::Ping => {}
-            _ => {
-                panic!("Invalid Packet Type");
-            }
-        }
+        assert!(matches!(
+            result,
+            Err(TcpContractReadFail::InvalidPayload(
+                PayloadError::PublishOptionsAmountMismatch { .. }
+            ))
+        ));
     }
 
     #[tokio::test]
-    async fn test_pong_packet() {
-        let tcp_packet = TcpContract::Pong;
+    async fn test_publish_packet_with_negative_messages_count() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let mut serialized_data = vec![PUBLISH];
+        crate::tcp_serializers::pascal_string::serialize(&mut serialized_data, "topic");
+        crate::tcp_serializers::i64::serialize(&mut serialized_data, 1);
+        crate::tcp_serializers::i32::serialize(&mut serialized_data, -1);
+
+        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
 
-        let serialized_data: Vec<u8> = tcp_packet.serialize(2);
         let mut socket_reader = SocketReaderInMem::new(serialized_data);
-        let attr = ConnectionAttributes::new(0);
 
-        let result = TcpContract::deserialize(&mut socket_reader, &attr)
-            .await
-            .unwrap();
+        let result = TcpContract::deserialize(&mut socket_reader, &attr).await;
 
-        match result {
-            TcpContract::Pong => {}
-            _ => {
-                panic!("Invalid Packet Type");
-            }
-        }
+        assert!(matches!(
+            result,
+            Err(TcpContractReadFail::InvalidPayload(
+                PayloadError::NegativeLength(-1)
+            ))
+        ));
     }
 
     #[tokio::test]
-    async fn test_greeting_packet() {
-        let test_app_name = "test_app";
-        let test_protocol_version = 2;
+    async fn test_publish_packet_with_deliver_not_before() {
+        const PROTOCOL_VERSION: i32 = 3;
 
-        let tcp_packet = TcpContract::Greeting {
-            name: test_app_name.to_string(),
-            protocol_version: test_protocol_version,
+        let tcp_packet = TcpContract::Publish {
+            topic_id: "topic".to_string(),
+            request_id: 1,
+            persist_immediately: false,
+            data_to_publish: PublishMessages::with_options(
+                vec![MessageToPublish {
+                    content: vec![1, 2, 3],
+                    headers: None,
+                }],
+                None,
+                vec![PublishMessageOptions {
+                    expiration: Some(MessageExpiration::At(2_000_000)),
+                    deliver_not_before: Some(1_000_000),
+                    dedup_key: None,
+                }],
+            )
+            .unwrap(),
         };
-        let serialized_data: Vec<u8> = tcp_packet.serialize(0);
-        let mut socket_reader = SocketReaderInMem::new(serialized_data);
 
-        let attr = ConnectionAttributes::new(0);
+        let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        attr.versions.set_packet_version(PUBLISH, 2);
+
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
 
         let result = TcpContract::deserialize(&mut socket_reader, &attr)
             .await
             .unwrap();
 
         match result {
-            TcpContract::Greeting {
-                name,
-                protocol_version,
+            TcpContract::Publish {
+                data_to_publish, ..
             } => {
-                assert_eq!(test_app_name, name);
-                assert_eq!(test_protocol_version, protocol_version);
+                let options = data_to_publish.get_options();
+                assert_eq!(1, options.len());
+                assert_eq!(Some(1_000_000), options[0].deliver_not_before);
+                assert_eq!(
+                    Some(MessageExpiration::At(2_000_000)),
+                    options[0].expiration
+                );
             }
             _ => {
                 panic!("Invalid Packet Type");
@@ -584,29 +4092,207 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_publish_packet_with_deliver_not_before_to_v1_peer() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let data_to_publish = PublishMessages::with_options(
+            vec![MessageToPublish {
+                content: vec![1, 2, 3],
+                headers: None,
+            }],
+            None,
+            vec![PublishMessageOptions {
+                expiration: None,
+                deliver_not_before: Some(1_000_000),
+                dedup_key: None,
+            }],
+        )
+        .unwrap();
+
+        let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        attr.versions.set_packet_version(PUBLISH, 1);
+
+        let result = TcpContract::compile_publish_payload_with_options(
+            "topic",
+            1,
+            data_to_publish.get_messages(),
+            false,
+            None,
+            data_to_publish.get_options(),
+            &attr.get(PUBLISH),
+        );
+
+        assert_eq!(Err(PayloadError::DeliverNotBeforeIsNotSupported(1)), result);
+        assert_eq!(
+            Err(PayloadError::DeliverNotBeforeIsNotSupported(1)),
+            data_to_publish.check_packet_version(1)
+        );
+
+        let tcp_packet = TcpContract::Publish {
+            topic_id: "topic".to_string(),
+            request_id: 1,
+            persist_immediately: false,
+            data_to_publish,
+        };
+
+        assert!(tcp_packet.serialize_with_attr(&attr).is_empty());
+    }
+
     #[tokio::test]
-    async fn test_publish_packet_v2() {
-        const PROTOCOL_VERSION: i32 = 2;
+    async fn test_publish_packet_batch_expires_before_deliver_not_before() {
+        const PROTOCOL_VERSION: i32 = 3;
 
-        let request_id_test = 1;
+        let data_to_publish = vec![MessageToPublish {
+            content: vec![1, 2, 3],
+            headers: None,
+        }];
 
-        let message_to_publish = MessageToPublish {
-            content: vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 0],
+        let options = vec![PublishMessageOptions {
+            expiration: None,
+            deliver_not_before: Some(2_000_000),
+            dedup_key: None,
+        }];
+
+        let batch_expiration = MessageExpiration::At(1_000_000);
+
+        let result = PublishMessages::with_options(
+            data_to_publish.clone(),
+            Some(batch_expiration.clone()),
+            options.clone(),
+        );
+
+        assert_eq!(
+            Some(PayloadError::ExpiresBeforeDeliverNotBefore {
+                expires: 1_000_000,
+                deliver_not_before: 2_000_000,
+            }),
+            result.err()
+        );
+
+        let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        attr.versions.set_packet_version(PUBLISH, 2);
+
+        let mut serialized_data = TcpContract::compile_publish_payload(
+            "topic",
+            1,
+            &data_to_publish,
+            false,
+            PROTOCOL_VERSION,
+        );
+        crate::tcp_serializers::message_expiration::serialize(
+            &mut serialized_data,
+            Some(&batch_expiration),
+        );
+        crate::tcp_serializers::messages_to_publish::serialize_options(
+            &mut serialized_data,
+            &options,
+            2,
+        )
+        .unwrap();
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr).await;
+
+        assert!(matches!(
+            result,
+            Err(TcpContractReadFail::InvalidPayload(
+                PayloadError::ExpiresBeforeDeliverNotBefore { .. }
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_publish_packet_expires_before_deliver_not_before() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let data_to_publish = vec![MessageToPublish {
+            content: vec![1, 2, 3],
             headers: None,
-        };
+        }];
 
-        let data_test = vec![message_to_publish];
-        let topic_test = String::from("test-topic");
-        let persist_test = true;
+        let options = vec![PublishMessageOptions {
+            expiration: Some(MessageExpiration::At(1_000_000)),
+            deliver_not_before: Some(2_000_000),
+            dedup_key: None,
+        }];
+
+        let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        attr.versions.set_packet_version(PUBLISH, 2);
+
+        let result = TcpContract::compile_publish_payload_with_options(
+            "topic",
+            1,
+            &data_to_publish,
+            false,
+            None,
+            &options,
+            &attr.get(PUBLISH),
+        );
+
+        assert_eq!(
+            Err(PayloadError::ExpiresBeforeDeliverNotBefore {
+                expires: 1_000_000,
+                deliver_not_before: 2_000_000,
+            }),
+            result
+        );
+
+        let mut serialized_data = TcpContract::compile_publish_payload(
+            "topic",
+            1,
+            &data_to_publish,
+            false,
+            PROTOCOL_VERSION,
+        );
+        crate::tcp_serializers::message_expiration::serialize(&mut serialized_data, None);
+        crate::tcp_serializers::messages_to_publish::serialize_options(
+            &mut serialized_data,
+            &options,
+            2,
+        )
+        .unwrap();
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr).await;
+
+        assert!(matches!(
+            result,
+            Err(TcpContractReadFail::InvalidPayload(
+                PayloadError::ExpiresBeforeDeliverNotBefore { .. }
+            ))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_publish_packet_with_dedup_key() {
+        const PROTOCOL_VERSION: i32 = 3;
 
         let tcp_packet = TcpContract::Publish {
-            data_to_publish: data_test,
-            persist_immediately: persist_test,
-            request_id: request_id_test,
-            topic_id: topic_test,
+            topic_id: "topic".to_string(),
+            request_id: 1,
+            persist_immediately: false,
+            data_to_publish: PublishMessages::with_options(
+                vec![MessageToPublish {
+                    content: vec![1, 2, 3],
+                    headers: None,
+                }],
+                None,
+                vec![PublishMessageOptions {
+                    expiration: None,
+                    deliver_not_before: None,
+                    dedup_key: Some("order-15".to_string()),
+                }],
+            )
+            .unwrap(),
         };
-        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
-        let serialized_data: Vec<u8> = tcp_packet.serialize(attr.protocol_version);
+
+        let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        attr.versions.set_packet_version(PUBLISH, 3);
+
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
 
         let mut socket_reader = SocketReaderInMem::new(serialized_data);
 
@@ -616,20 +4302,11 @@ mod tests {
 
         match result {
             TcpContract::Publish {
-                data_to_publish,
-                persist_immediately,
-                request_id,
-                topic_id,
+                data_to_publish, ..
             } => {
-                assert_eq!(request_id_test, request_id);
-                assert_eq!(String::from("test-topic"), topic_id);
-                assert_eq!(persist_test, persist_immediately);
-
-                let data_test = vec![vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 0]];
-
-                for index in 0..data_to_publish[0].content.len() {
-                    assert_eq!(data_test[0][index], data_to_publish[0].content[index]);
-                }
+                let options = data_to_publish.get_options();
+                assert_eq!(1, options.len());
+                assert_eq!(Some("order-15".to_string()), options[0].dedup_key);
             }
             _ => {
                 panic!("Invalid Packet Type");
@@ -637,34 +4314,75 @@ mod tests {
         }
     }
 
-    #[tokio::test]
-    async fn test_publish_packet_v3() {
+    #[test]
+    fn test_publish_packet_with_dedup_key_to_v2_peer() {
         const PROTOCOL_VERSION: i32 = 3;
 
-        let request_id_test = 1;
+        let data_to_publish = PublishMessages::with_options(
+            vec![MessageToPublish {
+                content: vec![1, 2, 3],
+                headers: None,
+            }],
+            None,
+            vec![PublishMessageOptions {
+                expiration: None,
+                deliver_not_before: None,
+                dedup_key: Some("order-15".to_string()),
+            }],
+        )
+        .unwrap();
 
-        let mut headers = HashMap::new();
-        headers.insert("key1".to_string(), "value1".to_string());
-        headers.insert("key2".to_string(), "value2".to_string());
+        let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        attr.versions.set_packet_version(PUBLISH, 2);
 
-        let message_to_publish = MessageToPublish {
-            content: vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 0],
-            headers: Some(headers),
-        };
+        let result = TcpContract::compile_publish_payload_with_options(
+            "topic",
+            1,
+            data_to_publish.get_messages(),
+            false,
+            None,
+            data_to_publish.get_options(),
+            &attr.get(PUBLISH),
+        );
 
-        let data_test = vec![message_to_publish];
-        let topic_test = String::from("test-topic");
-        let persist_test = true;
+        assert_eq!(Err(PayloadError::DedupKeyIsNotSupported(2)), result);
+
+        let mut serialized_data = Vec::new();
+        let result = crate::tcp_serializers::messages_to_publish::serialize_options(
+            &mut serialized_data,
+            data_to_publish.get_options(),
+            2,
+        );
+
+        assert_eq!(Err(PayloadError::DedupKeyIsNotSupported(2)), result);
+        assert!(serialized_data.is_empty());
 
         let tcp_packet = TcpContract::Publish {
-            data_to_publish: data_test,
-            persist_immediately: persist_test,
-            request_id: request_id_test,
-            topic_id: topic_test,
+            topic_id: "topic".to_string(),
+            request_id: 1,
+            persist_immediately: false,
+            data_to_publish,
         };
 
-        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
-        let serialized_data: Vec<u8> = tcp_packet.serialize(attr.protocol_version);
+        assert!(tcp_packet.serialize_with_attr(&attr).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_publish_detailed_response_packet() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let tcp_packet = TcpContract::PublishDetailedResponse {
+            request_id: 1,
+            statuses: vec![
+                PublishMessageStatus::Accepted,
+                PublishMessageStatus::Duplicate,
+            ],
+        };
+
+        let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        attr.versions.set_packet_version(PUBLISH, 3);
+
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
 
         let mut socket_reader = SocketReaderInMem::new(serialized_data);
 
@@ -673,29 +4391,18 @@ mod tests {
             .unwrap();
 
         match result {
-            TcpContract::Publish {
-                mut data_to_publish,
-                persist_immediately,
+            TcpContract::PublishDetailedResponse {
                 request_id,
-                topic_id,
+                statuses,
             } => {
-                assert_eq!(request_id_test, request_id);
-                assert_eq!(String::from("test-topic"), topic_id);
-                assert_eq!(persist_test, persist_immediately);
-
-                let data_test = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 0];
-
-                assert_eq!(1, data_to_publish.len());
-
-                let el0 = data_to_publish.remove(0);
-
-                let mut headers = el0.headers.unwrap();
-
-                assert_eq!(data_test, el0.content);
-                assert_eq!(2, headers.len());
-
-                assert_eq!("value1", headers.remove("key1").unwrap());
-                assert_eq!("value2", headers.remove("key2").unwrap());
+                assert_eq!(1, request_id);
+                assert_eq!(
+                    vec![
+                        PublishMessageStatus::Accepted,
+                        PublishMessageStatus::Duplicate
+                    ],
+                    statuses
+                );
             }
             _ => {
                 panic!("Invalid Packet Type");
@@ -704,19 +4411,18 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_publish_response_packet() {
-        const PROTOCOL_VERSION: i32 = 2;
-
-        let request_id_test = 1;
+    async fn test_publish_detailed_response_packet_to_old_client() {
+        const PROTOCOL_VERSION: i32 = 3;
 
-        let tcp_packet = TcpContract::PublishResponse {
-            request_id: request_id_test,
+        let tcp_packet = TcpContract::PublishDetailedResponse {
+            request_id: 1,
+            statuses: vec![PublishMessageStatus::Duplicate],
         };
 
         let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
-        attr.protocol_version = PROTOCOL_VERSION;
+        attr.versions.set_packet_version(PUBLISH, 2);
 
-        let serialized_data: Vec<u8> = tcp_packet.serialize(PROTOCOL_VERSION);
+        let serialized_data: Vec<u8> = tcp_packet.serialize_with_attr(&attr);
 
         let mut socket_reader = SocketReaderInMem::new(serialized_data);
 
@@ -726,7 +4432,7 @@ mod tests {
 
         match result {
             TcpContract::PublishResponse { request_id } => {
-                assert_eq!(request_id_test, request_id);
+                assert_eq!(1, request_id);
             }
             _ => {
                 panic!("Invalid Packet Type");
@@ -735,48 +4441,86 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_subscribe_packet() {
-        const PROTOCOL_VERSION: i32 = 2;
-        let queue_id_test = String::from("queue");
-        let topic_id_test = String::from("topic");
-        let queue_type_test = TopicQueueType::PermanentWithSingleConnection;
+    async fn test_publish_detailed_response_packet_with_unknown_status() {
+        const PROTOCOL_VERSION: i32 = 3;
 
-        let tcp_packet = TcpContract::Subscribe {
-            queue_id: queue_id_test,
-            topic_id: topic_id_test,
-            queue_type: queue_type_test,
-        };
+        let mut serialized_data = vec![PUBLISH_DETAILED_RESPONSE];
+        crate::tcp_serializers::i64::serialize(&mut serialized_data, 1);
+        crate::tcp_serializers::i32::serialize(&mut serialized_data, 1);
+        crate::tcp_serializers::byte::serialize(&mut serialized_data, 2);
 
-        let attr = ConnectionAttributes::new(PROTOCOL_VERSION);
-        let serialized_data: Vec<u8> = tcp_packet.serialize(PROTOCOL_VERSION);
+        let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        attr.versions.set_packet_version(PUBLISH, 3);
 
         let mut socket_reader = SocketReaderInMem::new(serialized_data);
 
-        let result = TcpContract::deserialize(&mut socket_reader, &attr)
-            .await
-            .unwrap();
+        let result = TcpContract::deserialize(&mut socket_reader, &attr).await;
 
-        match result {
-            TcpContract::Subscribe {
-                queue_id,
-                queue_type,
-                topic_id,
-            } => {
-                let queue_id_test = String::from("queue");
-                let topic_id_test = String::from("topic");
+        assert!(matches!(
+            result,
+            Err(TcpContractReadFail::InvalidPayload(
+                PayloadError::UnknownPublishMessageStatus(2)
+            ))
+        ));
 
-                assert_eq!(queue_id_test, queue_id);
-                assert_eq!(topic_id_test, topic_id);
-                match queue_type {
-                    TopicQueueType::PermanentWithSingleConnection => {}
-                    _ => {
-                        panic!("Invalid Queue Type");
-                    }
-                };
-            }
-            _ => {
-                panic!("Invalid Packet Type");
-            }
-        }
+        //Truncated packet is a socket level failure
+        let mut serialized_data = vec![PUBLISH_DETAILED_RESPONSE];
+        crate::tcp_serializers::i64::serialize(&mut serialized_data, 1);
+        crate::tcp_serializers::i32::serialize(&mut serialized_data, 1);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+
+        let result = TcpContract::deserialize(&mut socket_reader, &attr).await;
+
+        assert!(matches!(result, Err(TcpContractReadFail::Socket(_))));
+    }
+
+    #[test]
+    fn test_publish_packet_dedup_key_length() {
+        const PROTOCOL_VERSION: i32 = 3;
+
+        let data_to_publish = vec![MessageToPublish {
+            content: vec![1, 2, 3],
+            headers: None,
+        }];
+
+        let mut attr = ConnectionAttributes::new(PROTOCOL_VERSION);
+        attr.versions.set_packet_version(PUBLISH, 3);
+
+        let options = vec![PublishMessageOptions {
+            expiration: None,
+            deliver_not_before: None,
+            dedup_key: Some("k".repeat(crate::MAX_DEDUP_KEY_LEN)),
+        }];
+
+        let result = TcpContract::compile_publish_payload_with_options(
+            "topic",
+            1,
+            &data_to_publish,
+            false,
+            None,
+            &options,
+            &attr.get(PUBLISH),
+        );
+
+        assert!(result.is_ok());
+
+        let options = vec![PublishMessageOptions {
+            expiration: None,
+            deliver_not_before: None,
+            dedup_key: Some("k".repeat(crate::MAX_DEDUP_KEY_LEN + 1)),
+        }];
+
+        let result = TcpContract::compile_publish_payload_with_options(
+            "topic",
+            1,
+            &data_to_publish,
+            false,
+            None,
+            &options,
+            &attr.get(PUBLISH),
+        );
+
+        assert_eq!(Err(PayloadError::DedupKeyTooLong(256)), result);
     }
 }
@@ -14,3 +14,33 @@ pub const REJECT: u8 = 12;
 pub const ALL_MESSAGES_NOT_DELIVERED_CONFIRMATION: u8 = 13;
 pub const CONFIRM_SOME_MESSAGES_AS_OK: u8 = 14;
 pub const INTERMEDIARY_CONFIRM: u8 = 15; //Confirms some messages within Delivery but not complete Delivery
+pub const CONFIRM_SOME_MESSAGES_AS_FAIL: u8 = 16; //Fails some messages within Delivery with reason and redelivery delay
+pub const EXTEND_DELIVERY_LEASE: u8 = 17; //Extends delivery timeout without confirming messages
+pub const GRANT_CREDITS: u8 = 18; //Extends prefetch window of the subscriber
+pub const THROTTLE_PUBLISH: u8 = 19; //Server asks publisher to slow down. Sent only if client supports packet version 1+
+pub const RESUME_PUBLISH: u8 = 20; //Server allows publisher to publish at full speed again. Sent only if client supports THROTTLE_PUBLISH
+pub const UNSUBSCRIBE: u8 = 21;
+pub const UNSUBSCRIBE_RESPONSE: u8 = 22;
+pub const SUBSCRIBE_BY_PATTERN: u8 = 23;
+pub const PATTERN_TOPIC_SUBSCRIBED: u8 = 24; //Server notifies client about topic which matches pattern subscription
+pub const PAUSE_SUBSCRIPTION: u8 = 25;
+pub const RESUME_SUBSCRIPTION: u8 = 26;
+pub const FETCH_MESSAGES: u8 = 27;
+pub const FETCH_MESSAGES_RESPONSE: u8 = 28; //Messages are encoded the same way as NewMessages
+pub const PEEK_MESSAGES: u8 = 29;
+pub const PEEK_MESSAGES_RESPONSE: u8 = 30; //Messages are encoded the same way as NewMessages but delivery is not registered
+pub const DECLARE_QUEUE: u8 = 31;
+pub const DELETE_QUEUE: u8 = 32;
+pub const DELETE_TOPIC: u8 = 33;
+pub const PURGE_QUEUE: u8 = 34;
+pub const MANAGEMENT_RESPONSE: u8 = 35; //Response to DeclareQueue, DeleteQueue, DeleteTopic and PurgeQueue
+pub const CREATE_TOPIC_IF_NOT_EXISTS_RESPONSE: u8 = 36;
+pub const GET_QUEUE_STATS: u8 = 37;
+pub const GET_QUEUE_STATS_RESPONSE: u8 = 38;
+pub const LIST_TOPICS: u8 = 39;
+pub const LIST_TOPICS_RESPONSE: u8 = 40;
+pub const LIST_QUEUES: u8 = 41;
+pub const LIST_QUEUES_RESPONSE: u8 = 42;
+pub const LIST_CONNECTIONS: u8 = 43;
+pub const LIST_CONNECTIONS_RESPONSE: u8 = 44;
+pub const PUBLISH_DETAILED_RESPONSE: u8 = 45; //Sent instead of PublishResponse if client supports it
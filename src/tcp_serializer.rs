@@ -25,7 +25,7 @@ impl TcpSocketSerializer<TcpContract> for MySbTcpSerializer {
     const PING_PACKET_IS_SINGLETONE: bool = true;
 
     fn serialize(&self, contract: TcpContract) -> Vec<u8> {
-        contract.serialize(self.attr.protocol_version)
+        contract.serialize_with_attr(&self.attr)
     }
     fn get_ping(&self) -> TcpContract {
         TcpContract::Ping
@@ -34,7 +34,9 @@ impl TcpSocketSerializer<TcpContract> for MySbTcpSerializer {
         &mut self,
         socket_reader: &mut TSocketReader,
     ) -> Result<TcpContract, ReadingTcpContractFail> {
-        let result = TcpContract::deserialize(socket_reader, &self.attr).await?;
+        let result = TcpContract::deserialize(socket_reader, &self.attr)
+            .await
+            .map_err(|err| err.into_reading_fail())?;
         Ok(result)
     }
 
@@ -57,6 +59,6 @@ impl TcpSocketSerializer<TcpContract> for MySbTcpSerializer {
 
     fn serialize_ref(&self, contract: &TcpContract) -> Vec<u8> {
         let contract = contract.clone();
-        contract.serialize(self.attr.protocol_version)
+        contract.serialize_with_attr(&self.attr)
     }
 }
@@ -0,0 +1,63 @@
+use std::collections::HashMap;
+
+use my_tcp_sockets::socket_reader::{ReadingTcpContractFail, SocketReader};
+
+use crate::ConnectionInfo;
+
+pub fn serialize(data: &mut Vec<u8>, v: &[ConnectionInfo], packet_version: i32) {
+    super::i32::serialize(data, v.len() as i32);
+
+    for item in v {
+        super::i64::serialize(data, item.connection_id);
+        super::pascal_string::serialize(data, item.name.as_str());
+        super::i32::serialize(data, item.protocol_version);
+        super::pascal_string::serialize(data, item.ip.as_str());
+
+        if packet_version >= 1 {
+            super::byte::serialize(data, item.packet_versions.len() as u8);
+
+            for (packet_no, version) in &item.packet_versions {
+                super::byte::serialize(data, *packet_no);
+                super::i32::serialize(data, *version);
+            }
+        }
+    }
+}
+
+pub async fn deserialize<TSocketReader: SocketReader>(
+    reader: &mut TSocketReader,
+    packet_version: i32,
+) -> Result<Vec<ConnectionInfo>, ReadingTcpContractFail> {
+    let len = reader.read_i32().await?;
+
+    let mut result = Vec::new();
+
+    for _ in 0..len {
+        let connection_id = reader.read_i64().await?;
+        let name = super::pascal_string::deserialize(reader).await?;
+        let protocol_version = reader.read_i32().await?;
+        let ip = super::pascal_string::deserialize(reader).await?;
+
+        let mut packet_versions = HashMap::new();
+
+        if packet_version >= 1 {
+            let versions_len = reader.read_byte().await?;
+
+            for _ in 0..versions_len {
+                let packet_no = reader.read_byte().await?;
+                let version = reader.read_i32().await?;
+                packet_versions.insert(packet_no, version);
+            }
+        }
+
+        result.push(ConnectionInfo {
+            connection_id,
+            name,
+            protocol_version,
+            ip,
+            packet_versions,
+        });
+    }
+
+    Ok(result)
+}
@@ -12,7 +12,7 @@ pub async fn convert_from_raw(
 
         let mut attr = crate::ConnectionAttributes::new(version.protocol_version);
         let mut versions = HashMap::new();
-        versions.insert(tcp_message_id::NEW_MESSAGES, 1);
+        versions.insert(tcp_message_id::NEW_MESSAGES, version.packet_version);
         attr.versions.update(&versions);
 
         return crate::TcpContract::deserialize(&mut socket_reader, &attr)
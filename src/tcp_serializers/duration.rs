@@ -0,0 +1,58 @@
+use std::time::Duration;
+
+use my_tcp_sockets::socket_reader::SocketReader;
+
+use crate::{PayloadError, TcpContractReadFail};
+
+//Duration is transferred as milliseconds. Durations which do not fit into i64 are sent as i64::MAX
+pub fn serialize(data: &mut Vec<u8>, v: Duration) {
+    super::i64::serialize(data, v.as_millis().min(i64::MAX as u128) as i64);
+}
+
+pub async fn deserialize<TSocketReader: SocketReader>(
+    reader: &mut TSocketReader,
+) -> Result<Duration, TcpContractReadFail> {
+    let millis = reader.read_i64().await?;
+
+    if millis < 0 {
+        return Err(PayloadError::NegativeDuration(millis).into());
+    }
+
+    Ok(Duration::from_millis(millis as u64))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use my_tcp_sockets::socket_reader::SocketReaderInMem;
+
+    use crate::{PayloadError, TcpContractReadFail};
+
+    #[tokio::test]
+    pub async fn test_too_long_duration_is_clamped() {
+        let mut serialized_data = Vec::new();
+        super::serialize(&mut serialized_data, Duration::MAX);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+        let result = super::deserialize(&mut socket_reader).await.unwrap();
+
+        assert_eq!(Duration::from_millis(i64::MAX as u64), result);
+    }
+
+    #[tokio::test]
+    pub async fn test_negative_duration_is_rejected() {
+        let mut serialized_data = Vec::new();
+        crate::tcp_serializers::i64::serialize(&mut serialized_data, -1);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+        let result = super::deserialize(&mut socket_reader).await;
+
+        assert!(matches!(
+            result,
+            Err(TcpContractReadFail::InvalidPayload(
+                PayloadError::NegativeDuration(-1)
+            ))
+        ));
+    }
+}
@@ -0,0 +1,43 @@
+use my_tcp_sockets::socket_reader::SocketReader;
+
+use crate::{FailedMessages, TcpContractReadFail};
+
+pub fn serialize(data: &mut Vec<u8>, v: &[FailedMessages]) {
+    super::i32::serialize(data, v.len() as i32);
+
+    for item in v {
+        super::queue_with_intervals::serialize(data, &item.ranges);
+        super::i32::serialize(data, item.reason_code);
+        super::pascal_string::serialize(data, item.reason.as_str());
+        super::duration::serialize(data, item.redelivery_delay);
+        super::bool::serialize(data, item.poison);
+    }
+}
+
+//strict - ranges are validated the same way as ranges of other confirmation packets
+pub async fn deserialize<TSocketReader: SocketReader>(
+    reader: &mut TSocketReader,
+    strict: bool,
+) -> Result<Vec<FailedMessages>, TcpContractReadFail> {
+    let len = reader.read_i32().await?;
+
+    let mut result = Vec::new();
+
+    for _ in 0..len {
+        let ranges = super::queue_with_intervals::deserialize_with_mode(reader, strict).await?;
+        let reason_code = reader.read_i32().await?;
+        let reason = super::pascal_string::deserialize(reader).await?;
+        let redelivery_delay = super::duration::deserialize(reader).await?;
+        let poison = reader.read_bool().await?;
+
+        result.push(FailedMessages {
+            ranges,
+            reason_code,
+            reason,
+            redelivery_delay,
+            poison,
+        });
+    }
+
+    Ok(result)
+}
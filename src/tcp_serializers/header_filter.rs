@@ -0,0 +1,228 @@
+use my_tcp_sockets::socket_reader::SocketReader;
+
+use crate::{HeaderFilter, PayloadError, TcpContractReadFail};
+
+const EQUALS: u8 = 0;
+const IN: u8 = 1;
+const PREFIX: u8 = 2;
+const AND: u8 = 3;
+const OR: u8 = 4;
+
+//Protects us from stack overflow on malicious payload
+const MAX_DEPTH: usize = 32;
+
+//Filter is encoded into byte array so we can parse nested filters without async recursion
+pub fn serialize(data: &mut Vec<u8>, v: Option<&HeaderFilter>) {
+    match v {
+        Some(filter) => {
+            super::bool::serialize(data, true);
+
+            let mut encoded = Vec::new();
+            encode(&mut encoded, filter);
+            super::byte_array::serialize(data, encoded.as_slice());
+        }
+        None => {
+            super::bool::serialize(data, false);
+        }
+    }
+}
+
+pub async fn deserialize<TSocketReader: SocketReader>(
+    reader: &mut TSocketReader,
+) -> Result<Option<HeaderFilter>, TcpContractReadFail> {
+    if !reader.read_bool().await? {
+        return Ok(None);
+    }
+
+    let encoded = reader.read_byte_array().await?;
+
+    match decode(encoded.as_slice()) {
+        Some(filter) => Ok(Some(filter)),
+        None => Err(PayloadError::InvalidHeaderFilter.into()),
+    }
+}
+
+pub fn encode(dest: &mut Vec<u8>, filter: &HeaderFilter) {
+    match filter {
+        HeaderFilter::Equals { key, value } => {
+            dest.push(EQUALS);
+            super::pascal_string::serialize(dest, key);
+            super::pascal_string::serialize(dest, value);
+        }
+        HeaderFilter::In { key, values } => {
+            dest.push(IN);
+            super::pascal_string::serialize(dest, key);
+            super::i32::serialize(dest, values.len() as i32);
+            for value in values {
+                super::pascal_string::serialize(dest, value);
+            }
+        }
+        HeaderFilter::Prefix { key, prefix } => {
+            dest.push(PREFIX);
+            super::pascal_string::serialize(dest, key);
+            super::pascal_string::serialize(dest, prefix);
+        }
+        HeaderFilter::And(filters) => {
+            dest.push(AND);
+            encode_list(dest, filters);
+        }
+        HeaderFilter::Or(filters) => {
+            dest.push(OR);
+            encode_list(dest, filters);
+        }
+    }
+}
+
+fn encode_list(dest: &mut Vec<u8>, filters: &[HeaderFilter]) {
+    super::i32::serialize(dest, filters.len() as i32);
+    for filter in filters {
+        encode(dest, filter);
+    }
+}
+
+//Returns None if payload is malformed
+pub fn decode(src: &[u8]) -> Option<HeaderFilter> {
+    let mut reader = SliceReader { src, pos: 0 };
+    let result = decode_filter(&mut reader, 0)?;
+
+    if reader.pos != src.len() {
+        return None;
+    }
+
+    Some(result)
+}
+
+fn decode_filter(reader: &mut SliceReader, depth: usize) -> Option<HeaderFilter> {
+    if depth > MAX_DEPTH {
+        return None;
+    }
+
+    match reader.read_byte()? {
+        EQUALS => {
+            let key = reader.read_pascal_string()?;
+            let value = reader.read_pascal_string()?;
+            Some(HeaderFilter::Equals { key, value })
+        }
+        IN => {
+            let key = reader.read_pascal_string()?;
+            let len = reader.read_len()?;
+
+            let mut values = Vec::new();
+            for _ in 0..len {
+                values.push(reader.read_pascal_string()?);
+            }
+
+            Some(HeaderFilter::In { key, values })
+        }
+        PREFIX => {
+            let key = reader.read_pascal_string()?;
+            let prefix = reader.read_pascal_string()?;
+            Some(HeaderFilter::Prefix { key, prefix })
+        }
+        AND => {
+            let filters = decode_list(reader, depth)?;
+            Some(HeaderFilter::And(filters))
+        }
+        OR => {
+            let filters = decode_list(reader, depth)?;
+            Some(HeaderFilter::Or(filters))
+        }
+        _ => None,
+    }
+}
+
+fn decode_list(reader: &mut SliceReader, depth: usize) -> Option<Vec<HeaderFilter>> {
+    let len = reader.read_len()?;
+
+    let mut result = Vec::new();
+    for _ in 0..len {
+        result.push(decode_filter(reader, depth + 1)?);
+    }
+
+    Some(result)
+}
+
+struct SliceReader<'s> {
+    src: &'s [u8],
+    pos: usize,
+}
+
+impl<'s> SliceReader<'s> {
+    fn read_slice(&mut self, len: usize) -> Option<&'s [u8]> {
+        let result = self.src.get(self.pos..self.pos + len)?;
+        self.pos += len;
+        Some(result)
+    }
+
+    fn read_byte(&mut self) -> Option<u8> {
+        let result = self.read_slice(1)?;
+        Some(result[0])
+    }
+
+    fn read_len(&mut self) -> Option<usize> {
+        let result = self.read_slice(4)?;
+        let result = i32::from_le_bytes(result.try_into().ok()?);
+
+        if result < 0 {
+            return None;
+        }
+
+        Some(result as usize)
+    }
+
+    fn read_pascal_string(&mut self) -> Option<String> {
+        let len = self.read_byte()? as usize;
+        let result = self.read_slice(len)?;
+        String::from_utf8(result.to_vec()).ok()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::HeaderFilter;
+
+    #[test]
+    pub fn test_encode_decode() {
+        let filter = HeaderFilter::And(vec![
+            HeaderFilter::Equals {
+                key: "type".to_string(),
+                value: "OrderCreated".to_string(),
+            },
+            HeaderFilter::Or(vec![
+                HeaderFilter::In {
+                    key: "region".to_string(),
+                    values: vec!["eu".to_string(), "us".to_string()],
+                },
+                HeaderFilter::Prefix {
+                    key: "source".to_string(),
+                    prefix: "web-".to_string(),
+                },
+            ]),
+        ]);
+
+        let mut encoded = Vec::new();
+        super::encode(&mut encoded, &filter);
+
+        let result = super::decode(encoded.as_slice()).unwrap();
+
+        assert_eq!(filter, result);
+    }
+
+    #[test]
+    pub fn test_decode_malformed_payload() {
+        let filter = HeaderFilter::Equals {
+            key: "type".to_string(),
+            value: "OrderCreated".to_string(),
+        };
+
+        let mut encoded = Vec::new();
+        super::encode(&mut encoded, &filter);
+
+        assert!(super::decode(&encoded[..encoded.len() - 1]).is_none());
+
+        encoded.push(0);
+        assert!(super::decode(encoded.as_slice()).is_none());
+
+        assert!(super::decode(&[100]).is_none());
+    }
+}
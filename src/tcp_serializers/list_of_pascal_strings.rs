@@ -0,0 +1,23 @@
+use my_tcp_sockets::socket_reader::{ReadingTcpContractFail, SocketReader};
+
+pub fn serialize(data: &mut Vec<u8>, v: &[String]) {
+    super::i32::serialize(data, v.len() as i32);
+
+    for item in v {
+        super::pascal_string::serialize(data, item.as_str());
+    }
+}
+
+pub async fn deserialize<TSocketReader: SocketReader>(
+    reader: &mut TSocketReader,
+) -> Result<Vec<String>, ReadingTcpContractFail> {
+    let len = reader.read_i32().await?;
+
+    let mut result = Vec::new();
+
+    for _ in 0..len {
+        result.push(super::pascal_string::deserialize(reader).await?);
+    }
+
+    Ok(result)
+}
@@ -0,0 +1,41 @@
+use my_tcp_sockets::socket_reader::SocketReader;
+
+use crate::{MessageExpiration, PayloadError, TcpContractReadFail};
+
+const NO_EXPIRATION: u8 = 0;
+const EXPIRES_AT: u8 = 1;
+const EXPIRES_AFTER: u8 = 2;
+
+pub fn serialize(data: &mut Vec<u8>, v: Option<&MessageExpiration>) {
+    match v {
+        None => data.push(NO_EXPIRATION),
+        Some(MessageExpiration::At(expires)) => {
+            data.push(EXPIRES_AT);
+            super::i64::serialize(data, *expires);
+        }
+        Some(MessageExpiration::After(ttl)) => {
+            data.push(EXPIRES_AFTER);
+            super::duration::serialize(data, *ttl);
+        }
+    }
+}
+
+pub async fn deserialize<TSocketReader: SocketReader>(
+    reader: &mut TSocketReader,
+) -> Result<Option<MessageExpiration>, TcpContractReadFail> {
+    let expiration_type = reader.read_byte().await?;
+
+    match expiration_type {
+        NO_EXPIRATION => Ok(None),
+        EXPIRES_AT => {
+            let expires = reader.read_i64().await?;
+            Ok(Some(MessageExpiration::At(expires)))
+        }
+        EXPIRES_AFTER => {
+            let ttl = super::duration::deserialize(reader).await?;
+            Ok(Some(MessageExpiration::After(ttl)))
+        }
+        //Unknown expiration can carry payload we can not skip - so the whole packet is invalid
+        _ => Err(PayloadError::UnknownMessageExpiration(expiration_type).into()),
+    }
+}
@@ -2,7 +2,12 @@ use my_service_bus_abstractions::{MySbMessage, MyServiceBusMessage};
 
 use my_tcp_sockets::socket_reader::{ReadingTcpContractFail, SocketReader};
 
-use crate::PacketProtVer;
+use crate::{
+    DeliveredMessage, DeliveredMessageDetails, PacketProtVer, PayloadError, TcpContractReadFail,
+};
+
+//Amount of messages comes from the wire - so we do not trust it while allocating memory
+const MAX_PREALLOCATED_MESSAGES: usize = 1024;
 
 pub fn serialize(dest: &mut Vec<u8>, msg: &impl MyServiceBusMessage, version: &PacketProtVer) {
     if version.protocol_version < 3 {
@@ -15,7 +20,7 @@ pub fn serialize(dest: &mut Vec<u8>, msg: &impl MyServiceBusMessage, version: &P
 pub fn serialize_v2(dest: &mut Vec<u8>, msg: &impl MyServiceBusMessage, packet_version: i32) {
     crate::tcp_serializers::i64::serialize(dest, msg.get_id().get_value());
 
-    if packet_version == 1 {
+    if packet_version >= 1 {
         crate::tcp_serializers::i32::serialize(dest, msg.get_attempt_no());
     }
     super::byte_array::serialize(dest, msg.get_content());
@@ -28,6 +33,41 @@ pub fn serialize_v3(dest: &mut Vec<u8>, msg: &impl MyServiceBusMessage) {
     super::byte_array::serialize(dest, msg.get_content());
 }
 
+//Details go right after the message they belong to
+pub fn serialize_with_details(
+    dest: &mut Vec<u8>,
+    msg: &impl MyServiceBusMessage,
+    details: &DeliveredMessageDetails,
+    version: &PacketProtVer,
+) {
+    serialize(dest, msg, version);
+    serialize_details(dest, details, version.packet_version);
+}
+
+pub fn serialize_list(dest: &mut Vec<u8>, messages: &[DeliveredMessage], version: &PacketProtVer) {
+    crate::tcp_serializers::i32::serialize(dest, messages.len() as i32);
+
+    for msg in messages {
+        serialize_with_details(dest, &msg.message, &msg.details, version);
+    }
+}
+
+pub fn serialize_details(
+    dest: &mut Vec<u8>,
+    details: &DeliveredMessageDetails,
+    packet_version: i32,
+) {
+    if packet_version < 3 {
+        return;
+    }
+
+    crate::tcp_serializers::i64::serialize(dest, details.published);
+
+    if packet_version >= 4 {
+        crate::tcp_serializers::optional_i64::serialize(dest, details.expires);
+    }
+}
+
 pub async fn deserialize<TSocketReader: SocketReader>(
     socket_reader: &mut TSocketReader,
     version: &PacketProtVer,
@@ -39,13 +79,75 @@ pub async fn deserialize<TSocketReader: SocketReader>(
     return deserialize_v3(socket_reader).await;
 }
 
+pub async fn deserialize_with_details<TSocketReader: SocketReader>(
+    socket_reader: &mut TSocketReader,
+    version: &PacketProtVer,
+) -> Result<DeliveredMessage, ReadingTcpContractFail> {
+    let message = deserialize(socket_reader, version).await?;
+    let details = deserialize_details(socket_reader, version.packet_version).await?;
+
+    Ok(DeliveredMessage { message, details })
+}
+
+//Shared with other packets which carry a list of messages
+pub async fn deserialize_list_len<TSocketReader: SocketReader>(
+    socket_reader: &mut TSocketReader,
+) -> Result<usize, TcpContractReadFail> {
+    let records_len = socket_reader.read_i32().await?;
+
+    if records_len < 0 {
+        return Err(PayloadError::NegativeLength(records_len).into());
+    }
+
+    Ok(records_len as usize)
+}
+
+pub fn create_list<T>(records_len: usize) -> Vec<T> {
+    Vec::with_capacity(records_len.min(MAX_PREALLOCATED_MESSAGES))
+}
+
+pub async fn deserialize_list<TSocketReader: SocketReader>(
+    socket_reader: &mut TSocketReader,
+    version: &PacketProtVer,
+) -> Result<Vec<DeliveredMessage>, TcpContractReadFail> {
+    let records_len = deserialize_list_len(socket_reader).await?;
+
+    let mut result = create_list(records_len);
+
+    for _ in 0..records_len {
+        let msg = deserialize_with_details(socket_reader, version).await?;
+        result.push(msg);
+    }
+
+    Ok(result)
+}
+
+pub async fn deserialize_details<TSocketReader: SocketReader>(
+    socket_reader: &mut TSocketReader,
+    packet_version: i32,
+) -> Result<DeliveredMessageDetails, ReadingTcpContractFail> {
+    if packet_version < 3 {
+        return Ok(DeliveredMessageDetails::default());
+    }
+
+    let published = socket_reader.read_i64().await?;
+
+    let expires = if packet_version >= 4 {
+        crate::tcp_serializers::optional_i64::deserialize(socket_reader).await?
+    } else {
+        None
+    };
+
+    Ok(DeliveredMessageDetails { published, expires })
+}
+
 pub async fn deserialize_v2<TSocketReader: SocketReader>(
     socket_reader: &mut TSocketReader,
     packet_version: i32,
 ) -> Result<MySbMessage, ReadingTcpContractFail> {
     let id = socket_reader.read_i64().await?;
 
-    let attempt_no = if packet_version == 1 {
+    let attempt_no = if packet_version >= 1 {
         socket_reader.read_i32().await?
     } else {
         0
@@ -160,4 +262,27 @@ mod test {
 
         assert_eq!("value1", headers.get("key1").unwrap());
     }
+
+    #[tokio::test]
+    pub async fn test_list_with_invalid_length() {
+        let version = PacketProtVer {
+            protocol_version: 3,
+            packet_version: 1,
+        };
+
+        let mut serialized_data = Vec::new();
+        crate::tcp_serializers::i32::serialize(&mut serialized_data, -1);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+        let result = super::deserialize_list(&mut socket_reader, &version).await;
+        assert!(result.is_err());
+
+        //Declared amount is not preallocated - so reading fails on lack of data
+        let mut serialized_data = Vec::new();
+        crate::tcp_serializers::i32::serialize(&mut serialized_data, i32::MAX);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+        let result = super::deserialize_list(&mut socket_reader, &version).await;
+        assert!(result.is_err());
+    }
 }
@@ -1,4 +1,7 @@
 use my_service_bus_abstractions::publisher::MessageToPublish;
+use my_tcp_sockets::socket_reader::SocketReader;
+
+use crate::{MessageExpiration, PayloadError, PublishMessageOptions, TcpContractReadFail};
 
 pub fn serialize(data: &mut Vec<u8>, v: &[MessageToPublish], protocol_version: i32) {
     if protocol_version < 3 {
@@ -26,3 +29,88 @@ pub fn serialize_v3(data: &mut Vec<u8>, v: &[MessageToPublish]) {
         super::byte_array::serialize(data, &item.content);
     }
 }
+
+//Options are either empty or one per message in the same order as messages.
+//They are written after the batch expiration - at the very end of the Publish packet.
+//Nothing is written if some of the options can not be encoded by the packet version
+pub fn serialize_options(
+    data: &mut Vec<u8>,
+    v: &[PublishMessageOptions],
+    packet_version: i32,
+) -> Result<(), PayloadError> {
+    for item in v {
+        item.check_packet_version(packet_version)?;
+    }
+
+    super::i32::serialize(data, v.len() as i32);
+
+    for item in v {
+        super::message_expiration::serialize(data, item.expiration.as_ref());
+
+        if packet_version >= 2 {
+            super::optional_i64::serialize(data, item.deliver_not_before);
+        }
+
+        if packet_version >= 3 {
+            super::optional_pascal_string::serialize(data, item.dedup_key.as_deref());
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn deserialize_options<TSocketReader: SocketReader>(
+    reader: &mut TSocketReader,
+    messages_amount: usize,
+    batch_expiration: Option<&MessageExpiration>,
+    packet_version: i32,
+) -> Result<Vec<PublishMessageOptions>, TcpContractReadFail> {
+    let len = reader.read_i32().await?;
+
+    check_options_amount(len, messages_amount)?;
+
+    let mut result = Vec::with_capacity(len as usize);
+
+    for _ in 0..len {
+        let expiration = super::message_expiration::deserialize(reader).await?;
+
+        let deliver_not_before = if packet_version >= 2 {
+            super::optional_i64::deserialize(reader).await?
+        } else {
+            None
+        };
+
+        let dedup_key = if packet_version >= 3 {
+            super::optional_pascal_string::deserialize(reader).await?
+        } else {
+            None
+        };
+
+        let item = PublishMessageOptions {
+            expiration,
+            deliver_not_before,
+            dedup_key,
+        };
+
+        item.validate(batch_expiration)?;
+
+        result.push(item);
+    }
+
+    Ok(result)
+}
+
+//Same check is done by both sides
+pub fn check_options_amount(
+    options_amount: i32,
+    messages_amount: usize,
+) -> Result<(), PayloadError> {
+    if options_amount < 0 || (options_amount != 0 && options_amount as usize != messages_amount) {
+        return Err(PayloadError::PublishOptionsAmountMismatch {
+            options_amount,
+            messages_amount,
+        });
+    }
+
+    Ok(())
+}
@@ -3,13 +3,28 @@ mod convert_from_raw;
 pub mod bool;
 pub mod byte;
 pub mod byte_array;
+pub mod connection_info;
+pub mod duration;
+pub mod failed_messages;
+pub mod header_filter;
 pub mod i32;
 pub mod i64;
 pub mod legacy_long;
 pub mod list_of_byte_arrays;
+pub mod list_of_pascal_strings;
+pub mod message_expiration;
 pub mod message_headers;
 pub mod messages_to_deliver;
 pub mod messages_to_publish;
+pub mod optional_i64;
+pub mod optional_pascal_string;
 pub mod pascal_string;
+pub mod prefetch_limit;
+pub mod queue_info;
+pub mod queue_lag;
+pub mod queue_stats;
 pub mod queue_with_intervals;
+pub mod subscribe_response_details;
+pub mod subscribe_start_position;
+pub mod topic_settings;
 pub use convert_from_raw::convert_from_raw;
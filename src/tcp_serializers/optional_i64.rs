@@ -0,0 +1,24 @@
+use my_tcp_sockets::socket_reader::{ReadingTcpContractFail, SocketReader};
+
+pub fn serialize(data: &mut Vec<u8>, v: Option<i64>) {
+    match v {
+        Some(v) => {
+            super::bool::serialize(data, true);
+            super::i64::serialize(data, v);
+        }
+        None => {
+            super::bool::serialize(data, false);
+        }
+    }
+}
+
+pub async fn deserialize<TSocketReader: SocketReader>(
+    reader: &mut TSocketReader,
+) -> Result<Option<i64>, ReadingTcpContractFail> {
+    if !reader.read_bool().await? {
+        return Ok(None);
+    }
+
+    let result = reader.read_i64().await?;
+    Ok(Some(result))
+}
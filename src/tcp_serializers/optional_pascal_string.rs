@@ -0,0 +1,24 @@
+use my_tcp_sockets::socket_reader::{ReadingTcpContractFail, SocketReader};
+
+pub fn serialize(data: &mut Vec<u8>, v: Option<&str>) {
+    match v {
+        Some(v) => {
+            super::bool::serialize(data, true);
+            super::pascal_string::serialize(data, v);
+        }
+        None => {
+            super::bool::serialize(data, false);
+        }
+    }
+}
+
+pub async fn deserialize<TSocketReader: SocketReader>(
+    reader: &mut TSocketReader,
+) -> Result<Option<String>, ReadingTcpContractFail> {
+    if !reader.read_bool().await? {
+        return Ok(None);
+    }
+
+    let result = super::pascal_string::deserialize(reader).await?;
+    Ok(Some(result))
+}
@@ -0,0 +1,32 @@
+use my_tcp_sockets::socket_reader::{ReadingTcpContractFail, SocketReader};
+
+use crate::PrefetchLimit;
+
+pub fn serialize(data: &mut Vec<u8>, v: Option<&PrefetchLimit>) {
+    match v {
+        Some(v) => {
+            super::bool::serialize(data, true);
+            super::i32::serialize(data, v.max_messages);
+            super::i64::serialize(data, v.max_bytes);
+        }
+        None => {
+            super::bool::serialize(data, false);
+        }
+    }
+}
+
+pub async fn deserialize<TSocketReader: SocketReader>(
+    reader: &mut TSocketReader,
+) -> Result<Option<PrefetchLimit>, ReadingTcpContractFail> {
+    if !reader.read_bool().await? {
+        return Ok(None);
+    }
+
+    let max_messages = reader.read_i32().await?;
+    let max_bytes = reader.read_i64().await?;
+
+    Ok(Some(PrefetchLimit {
+        max_messages,
+        max_bytes,
+    }))
+}
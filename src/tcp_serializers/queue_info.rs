@@ -0,0 +1,33 @@
+use my_service_bus_abstractions::subscriber::TopicQueueType;
+use my_tcp_sockets::socket_reader::{ReadingTcpContractFail, SocketReader};
+
+use crate::QueueInfo;
+
+pub fn serialize(data: &mut Vec<u8>, v: &[QueueInfo]) {
+    super::i32::serialize(data, v.len() as i32);
+
+    for item in v {
+        super::pascal_string::serialize(data, item.queue_id.as_str());
+        super::byte::serialize(data, item.queue_type.clone().into_u8());
+    }
+}
+
+pub async fn deserialize<TSocketReader: SocketReader>(
+    reader: &mut TSocketReader,
+) -> Result<Vec<QueueInfo>, ReadingTcpContractFail> {
+    let len = reader.read_i32().await?;
+
+    let mut result = Vec::new();
+
+    for _ in 0..len {
+        let queue_id = super::pascal_string::deserialize(reader).await?;
+        let queue_type = TopicQueueType::from_u8(reader.read_byte().await?);
+
+        result.push(QueueInfo {
+            queue_id,
+            queue_type,
+        });
+    }
+
+    Ok(result)
+}
@@ -0,0 +1,32 @@
+use my_tcp_sockets::socket_reader::{ReadingTcpContractFail, SocketReader};
+
+use crate::QueueLag;
+
+pub fn serialize(data: &mut Vec<u8>, v: Option<&QueueLag>) {
+    match v {
+        Some(v) => {
+            super::bool::serialize(data, true);
+            super::i64::serialize(data, v.remaining_messages);
+            super::optional_i64::serialize(data, v.newest_message_id);
+        }
+        None => {
+            super::bool::serialize(data, false);
+        }
+    }
+}
+
+pub async fn deserialize<TSocketReader: SocketReader>(
+    reader: &mut TSocketReader,
+) -> Result<Option<QueueLag>, ReadingTcpContractFail> {
+    if !reader.read_bool().await? {
+        return Ok(None);
+    }
+
+    let remaining_messages = reader.read_i64().await?;
+    let newest_message_id = super::optional_i64::deserialize(reader).await?;
+
+    Ok(Some(QueueLag {
+        remaining_messages,
+        newest_message_id,
+    }))
+}
@@ -0,0 +1,47 @@
+use my_tcp_sockets::socket_reader::{ReadingTcpContractFail, SocketReader};
+
+use crate::QueueStats;
+
+pub fn serialize(data: &mut Vec<u8>, v: &[QueueStats]) {
+    super::i32::serialize(data, v.len() as i32);
+
+    for item in v {
+        super::pascal_string::serialize(data, item.queue_id.as_str());
+        super::i64::serialize(data, item.messages_amount);
+        super::optional_i64::serialize(data, item.first_message_id);
+        super::optional_i64::serialize(data, item.last_message_id);
+        super::i64::serialize(data, item.in_flight_amount);
+        super::i32::serialize(data, item.subscribers_amount);
+        super::i32::serialize(data, item.publish_messages_per_second);
+    }
+}
+
+pub async fn deserialize<TSocketReader: SocketReader>(
+    reader: &mut TSocketReader,
+) -> Result<Vec<QueueStats>, ReadingTcpContractFail> {
+    let len = reader.read_i32().await?;
+
+    let mut result = Vec::new();
+
+    for _ in 0..len {
+        let queue_id = super::pascal_string::deserialize(reader).await?;
+        let messages_amount = reader.read_i64().await?;
+        let first_message_id = super::optional_i64::deserialize(reader).await?;
+        let last_message_id = super::optional_i64::deserialize(reader).await?;
+        let in_flight_amount = reader.read_i64().await?;
+        let subscribers_amount = reader.read_i32().await?;
+        let publish_messages_per_second = reader.read_i32().await?;
+
+        result.push(QueueStats {
+            queue_id,
+            messages_amount,
+            first_message_id,
+            last_message_id,
+            in_flight_amount,
+            subscribers_amount,
+            publish_messages_per_second,
+        });
+    }
+
+    Ok(result)
+}
@@ -1,6 +1,8 @@
 use my_service_bus_abstractions::queue_with_intervals::QueueIndexRange;
 use my_tcp_sockets::socket_reader::{ReadingTcpContractFail, SocketReader};
 
+use crate::{PayloadError, TcpContractReadFail};
+
 pub fn serialize(payload: &mut Vec<u8>, value: &Vec<QueueIndexRange>) {
     super::i32::serialize(payload, value.len() as i32);
 
@@ -26,3 +28,78 @@ pub async fn deserialize<T: SocketReader>(
 
     Ok(result)
 }
+
+//Rejects ranges which are not sorted, overlap or have from_id > to_id
+pub async fn deserialize_strict<T: SocketReader>(
+    reader: &mut T,
+) -> Result<Vec<QueueIndexRange>, TcpContractReadFail> {
+    let result = deserialize(reader).await?;
+
+    if let Err(err) = crate::queue_index_ranges::validate(&result) {
+        return Err(PayloadError::from(err).into());
+    }
+
+    Ok(result)
+}
+
+//Strict mode is enabled per connection with ConnectionAttributes::strict_queue_ranges
+pub async fn deserialize_with_mode<T: SocketReader>(
+    reader: &mut T,
+    strict: bool,
+) -> Result<Vec<QueueIndexRange>, TcpContractReadFail> {
+    if strict {
+        deserialize_strict(reader).await
+    } else {
+        Ok(deserialize(reader).await?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use my_service_bus_abstractions::queue_with_intervals::QueueIndexRange;
+    use my_tcp_sockets::socket_reader::SocketReaderInMem;
+
+    #[tokio::test]
+    pub async fn test_strict_deserialize() {
+        let ranges = vec![
+            QueueIndexRange {
+                from_id: 1,
+                to_id: 3,
+            },
+            QueueIndexRange {
+                from_id: 5,
+                to_id: 7,
+            },
+        ];
+
+        let mut serialized_data = Vec::new();
+        super::serialize(&mut serialized_data, &ranges);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+        let result = super::deserialize_strict(&mut socket_reader).await.unwrap();
+
+        assert_eq!(2, result.len());
+    }
+
+    #[tokio::test]
+    pub async fn test_strict_deserialize_rejects_overlapping_ranges() {
+        let ranges = vec![
+            QueueIndexRange {
+                from_id: 5,
+                to_id: 7,
+            },
+            QueueIndexRange {
+                from_id: 1,
+                to_id: 3,
+            },
+        ];
+
+        let mut serialized_data = Vec::new();
+        super::serialize(&mut serialized_data, &ranges);
+
+        let mut socket_reader = SocketReaderInMem::new(serialized_data);
+        let result = super::deserialize_strict(&mut socket_reader).await;
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,42 @@
+use my_service_bus_abstractions::subscriber::TopicQueueType;
+use my_tcp_sockets::socket_reader::SocketReader;
+
+use crate::{SubscribeResponseDetails, SubscribeStatus, TcpContractReadFail};
+
+pub fn serialize(data: &mut Vec<u8>, v: Option<SubscribeResponseDetails>) {
+    match v {
+        Some(v) => {
+            super::bool::serialize(data, true);
+            super::byte::serialize(data, v.status.into_u8());
+            super::optional_pascal_string::serialize(data, v.error.as_deref());
+            super::optional_i64::serialize(data, v.first_message_id);
+            super::optional_i64::serialize(data, v.last_message_id);
+            super::byte::serialize(data, v.queue_type.into_u8());
+        }
+        None => {
+            super::bool::serialize(data, false);
+        }
+    }
+}
+
+pub async fn deserialize<TSocketReader: SocketReader>(
+    reader: &mut TSocketReader,
+) -> Result<Option<SubscribeResponseDetails>, TcpContractReadFail> {
+    if !reader.read_bool().await? {
+        return Ok(None);
+    }
+
+    let status = SubscribeStatus::from_u8(reader.read_byte().await?)?;
+    let error = super::optional_pascal_string::deserialize(reader).await?;
+    let first_message_id = super::optional_i64::deserialize(reader).await?;
+    let last_message_id = super::optional_i64::deserialize(reader).await?;
+    let queue_type = TopicQueueType::from_u8(reader.read_byte().await?);
+
+    Ok(Some(SubscribeResponseDetails {
+        status,
+        error,
+        first_message_id,
+        last_message_id,
+        queue_type,
+    }))
+}
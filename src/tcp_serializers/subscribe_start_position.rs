@@ -0,0 +1,47 @@
+use my_tcp_sockets::socket_reader::SocketReader;
+
+use crate::{PayloadError, SubscribeStartPosition, TcpContractReadFail};
+
+const SERVER_DEFAULT: u8 = 0;
+const EARLIEST: u8 = 1;
+const LATEST: u8 = 2;
+const FROM_MESSAGE_ID: u8 = 3;
+const FROM_TIMESTAMP: u8 = 4;
+
+pub fn serialize(data: &mut Vec<u8>, v: &SubscribeStartPosition) {
+    match v {
+        SubscribeStartPosition::ServerDefault => data.push(SERVER_DEFAULT),
+        SubscribeStartPosition::Earliest => data.push(EARLIEST),
+        SubscribeStartPosition::Latest => data.push(LATEST),
+        SubscribeStartPosition::FromMessageId(message_id) => {
+            data.push(FROM_MESSAGE_ID);
+            super::i64::serialize(data, *message_id);
+        }
+        SubscribeStartPosition::FromTimestamp(timestamp) => {
+            data.push(FROM_TIMESTAMP);
+            super::i64::serialize(data, *timestamp);
+        }
+    }
+}
+
+pub async fn deserialize<TSocketReader: SocketReader>(
+    reader: &mut TSocketReader,
+) -> Result<SubscribeStartPosition, TcpContractReadFail> {
+    let position_type = reader.read_byte().await?;
+
+    match position_type {
+        SERVER_DEFAULT => Ok(SubscribeStartPosition::ServerDefault),
+        EARLIEST => Ok(SubscribeStartPosition::Earliest),
+        LATEST => Ok(SubscribeStartPosition::Latest),
+        FROM_MESSAGE_ID => {
+            let message_id = reader.read_i64().await?;
+            Ok(SubscribeStartPosition::FromMessageId(message_id))
+        }
+        FROM_TIMESTAMP => {
+            let timestamp = reader.read_i64().await?;
+            Ok(SubscribeStartPosition::FromTimestamp(timestamp))
+        }
+        //Unknown position can carry payload we can not skip - so the whole packet is invalid
+        _ => Err(PayloadError::UnknownSubscribeStartPosition(position_type).into()),
+    }
+}
@@ -0,0 +1,105 @@
+use my_tcp_sockets::socket_reader::SocketReader;
+
+use crate::{TcpContractReadFail, TopicPersistence, TopicSettings};
+
+pub fn serialize(data: &mut Vec<u8>, v: Option<&TopicSettings>, packet_version: i32) {
+    match v {
+        Some(v) => {
+            super::bool::serialize(data, true);
+            serialize_settings(data, v, packet_version);
+        }
+        None => {
+            super::bool::serialize(data, false);
+        }
+    }
+}
+
+pub async fn deserialize<TSocketReader: SocketReader>(
+    reader: &mut TSocketReader,
+    packet_version: i32,
+) -> Result<Option<TopicSettings>, TcpContractReadFail> {
+    if !reader.read_bool().await? {
+        return Ok(None);
+    }
+
+    let result = deserialize_settings(reader, packet_version).await?;
+    Ok(Some(result))
+}
+
+pub fn serialize_settings(data: &mut Vec<u8>, v: &TopicSettings, packet_version: i32) {
+    super::optional_i64::serialize(data, v.max_messages_amount);
+
+    if packet_version < 2 {
+        super::bool::serialize(data, v.persistence != TopicPersistence::InMemory);
+        return;
+    }
+
+    super::byte::serialize(data, v.persistence.into_u8());
+    super::optional_i64::serialize(data, v.max_bytes);
+
+    match v.message_ttl {
+        Some(message_ttl) => {
+            super::bool::serialize(data, true);
+            super::duration::serialize(data, message_ttl);
+        }
+        None => {
+            super::bool::serialize(data, false);
+        }
+    }
+
+    match v.max_message_size {
+        Some(max_message_size) => {
+            super::bool::serialize(data, true);
+            super::i32::serialize(data, max_message_size);
+        }
+        None => {
+            super::bool::serialize(data, false);
+        }
+    }
+}
+
+pub async fn deserialize_settings<TSocketReader: SocketReader>(
+    reader: &mut TSocketReader,
+    packet_version: i32,
+) -> Result<TopicSettings, TcpContractReadFail> {
+    let max_messages_amount = super::optional_i64::deserialize(reader).await?;
+
+    if packet_version < 2 {
+        let persistence = if reader.read_bool().await? {
+            TopicPersistence::Persistent
+        } else {
+            TopicPersistence::InMemory
+        };
+
+        return Ok(TopicSettings {
+            max_messages_amount,
+            persistence,
+            max_bytes: None,
+            message_ttl: None,
+            max_message_size: None,
+        });
+    }
+
+    let persistence = TopicPersistence::from_u8(reader.read_byte().await?)?;
+    let max_bytes = super::optional_i64::deserialize(reader).await?;
+
+    let message_ttl = if reader.read_bool().await? {
+        Some(super::duration::deserialize(reader).await?)
+    } else {
+        None
+    };
+
+    let max_message_size = if reader.read_bool().await? {
+        Some(reader.read_i32().await?)
+    } else {
+        None
+    };
+
+    Ok(TopicSettings {
+        max_messages_amount,
+        persistence,
+        max_bytes,
+        message_ttl,
+        max_message_size,
+    })
+}
@@ -0,0 +1,107 @@
+//Glob-style topic pattern. Client and server have to use the same matcher to agree on pattern semantics:
+// * - any sequence of characters (including empty one and dots)
+// ? - exactly one character
+//Any other character matches itself. Matching is case sensitive
+pub fn is_match(pattern: &str, topic_id: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let topic_id: Vec<char> = topic_id.chars().collect();
+
+    let mut pattern_pos = 0;
+    let mut topic_pos = 0;
+
+    //Position of the last '*' in pattern and position in topic it started to match from
+    let mut star: Option<(usize, usize)> = None;
+
+    while topic_pos < topic_id.len() {
+        //'*' has to be checked first - otherwise it matches '*' character of topic literally and never backtracks
+        if pattern_pos < pattern.len() && pattern[pattern_pos] == '*' {
+            star = Some((pattern_pos, topic_pos));
+            pattern_pos += 1;
+            continue;
+        }
+
+        if pattern_pos < pattern.len()
+            && (pattern[pattern_pos] == '?' || pattern[pattern_pos] == topic_id[topic_pos])
+        {
+            pattern_pos += 1;
+            topic_pos += 1;
+            continue;
+        }
+
+        match star {
+            Some((star_pattern_pos, star_topic_pos)) => {
+                pattern_pos = star_pattern_pos + 1;
+                topic_pos = star_topic_pos + 1;
+                star = Some((star_pattern_pos, topic_pos));
+            }
+            None => return false,
+        }
+    }
+
+    while pattern_pos < pattern.len() && pattern[pattern_pos] == '*' {
+        pattern_pos += 1;
+    }
+
+    pattern_pos == pattern.len()
+}
+
+pub fn is_pattern(topic_id: &str) -> bool {
+    topic_id.contains('*') || topic_id.contains('?')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_match() {
+        assert!(is_match("orders", "orders"));
+        assert!(!is_match("orders", "orders.created"));
+        assert!(!is_match("orders", "Orders"));
+    }
+
+    #[test]
+    fn test_star() {
+        assert!(is_match("orders.*", "orders.created"));
+        assert!(is_match("orders.*", "orders."));
+        assert!(is_match("orders.*", "orders.eu.created"));
+        assert!(!is_match("orders.*", "orders"));
+        assert!(!is_match("orders.*", "payments.created"));
+
+        assert!(is_match("*.created", "orders.created"));
+        assert!(is_match("*", ""));
+        assert!(is_match("orders.*.created", "orders.eu.created"));
+        assert!(!is_match("orders.*.created", "orders.eu.updated"));
+    }
+
+    #[test]
+    fn test_question_mark() {
+        assert!(is_match("orders-v?", "orders-v1"));
+        assert!(!is_match("orders-v?", "orders-v"));
+        assert!(!is_match("orders-v?", "orders-v12"));
+        assert!(is_match("orders-v?*", "orders-v12"));
+    }
+
+    #[test]
+    fn test_topic_with_wildcard_characters() {
+        assert!(is_match("*", "*x"));
+        assert!(is_match("*", "*"));
+        assert!(is_match("*x", "*x"));
+        assert!(is_match("*x", "**x"));
+        assert!(is_match("a*", "a*b"));
+        assert!(!is_match("*y", "*x"));
+
+        assert!(is_match("?", "?"));
+        assert!(is_match("?", "*"));
+        assert!(is_match("orders-?", "orders-?"));
+        assert!(is_match("orders-*", "orders-?x"));
+        assert!(!is_match("orders-?", "orders-??"));
+    }
+
+    #[test]
+    fn test_is_pattern() {
+        assert!(is_pattern("orders.*"));
+        assert!(is_pattern("orders-v?"));
+        assert!(!is_pattern("orders"));
+    }
+}